@@ -11,7 +11,7 @@ set -euo pipefail
 SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
 REPO_ROOT="$(cd "${SCRIPT_DIR}/.." && pwd)"
 
-ALL_FEATURES="bin-scribble-cli,bin-model-downloader,bin-scribble-server"
+ALL_FEATURES="bin-scribble-cli,bin-model-downloader,bin-scribble-server,testing,openai,remote,tokio"
 
 cd "$REPO_ROOT"
 
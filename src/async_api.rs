@@ -0,0 +1,365 @@
+//! Async wrappers around [`Scribble`] for Tokio callers.
+//!
+//! Transcription is CPU-bound and runs on Tokio's blocking thread pool; these wrappers bridge
+//! async I/O to it so callers do not have to juggle `spawn_blocking` and `SyncIoBridge`
+//! themselves.
+
+use std::io::{Read, Seek};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, ready};
+
+use futures_util::Stream;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio_util::io::SyncIoBridge;
+
+use crate::Result;
+use crate::backend::Backend;
+use crate::cancel::CancellationToken;
+use crate::decoder::DecodeInput;
+use crate::opts::Opts;
+use crate::scribble::Scribble;
+use crate::segment_encoder::SegmentEncoder;
+use crate::segments::Segment;
+
+/// Segments buffered between the transcription thread and a slow consumer.
+const SEGMENT_QUEUE_LEN: usize = 64;
+
+impl<B> Scribble<B>
+where
+    B: Backend + Send + Sync + 'static,
+{
+    /// Async version of [`Scribble::transcribe`].
+    ///
+    /// Decoding and inference run on Tokio's blocking thread pool. `w` is shut down once the
+    /// output is complete.
+    ///
+    /// Dropping the returned future cancels the transcription via `opts.cancellation_token`
+    /// (one is created when `None`), so abandoned requests stop instead of running to the end.
+    pub async fn transcribe_async<R, W>(self: &Arc<Self>, r: R, w: W, opts: &Opts) -> Result<()>
+    where
+        R: AsyncRead + Unpin + Send + 'static,
+        W: AsyncWrite + Unpin + Send + 'static,
+    {
+        let input = DecodeInput::Stream(Box::new(SyncIoBridge::new(r)));
+        self.transcribe_input_async(input, w, opts).await
+    }
+
+    /// Async version of [`Scribble::transcribe_seekable`].
+    ///
+    /// `r` is a blocking `Read + Seek` input (typically a `std::fs::File`, e.g. an upload spooled
+    /// to a temporary file); it is only read on the blocking thread pool. Otherwise behaves like
+    /// [`Scribble::transcribe_async`].
+    pub async fn transcribe_seekable_async<R, W>(
+        self: &Arc<Self>,
+        r: R,
+        w: W,
+        opts: &Opts,
+    ) -> Result<()>
+    where
+        R: Read + Seek + Send + 'static,
+        W: AsyncWrite + Unpin + Send + 'static,
+    {
+        let input = DecodeInput::Seekable(Box::new(r));
+        self.transcribe_input_async(input, w, opts).await
+    }
+
+    async fn transcribe_input_async<W>(
+        self: &Arc<Self>,
+        input: DecodeInput,
+        w: W,
+        opts: &Opts,
+    ) -> Result<()>
+    where
+        W: AsyncWrite + Unpin + Send + 'static,
+    {
+        let (opts, mut guard) = with_cancel_guard(opts);
+        let scribble = Arc::clone(self);
+        let mut output = SyncIoBridge::new(w);
+
+        let handle = tokio::task::spawn_blocking(move || {
+            scribble.transcribe_input(input, &mut output, &opts)?;
+            output.shutdown().map_err(|err| crate::Error::Io {
+                message: "failed to shut down output".to_owned(),
+                source: err,
+            })
+        });
+
+        let res = join(handle).await;
+        guard.disarm();
+        res
+    }
+
+    /// Transcribe `r` and yield segments as an async [`Stream`].
+    ///
+    /// Only final segments are yielded; partial results are not. A failure is yielded as the
+    /// last item. Unsupported options are rejected up front (see [`Scribble::validate_opts`]).
+    ///
+    /// Must be called from within a Tokio runtime. Dropping the stream cancels the
+    /// transcription via `opts.cancellation_token` (one is created when `None`).
+    pub fn transcribe_stream<R>(self: &Arc<Self>, r: R, opts: &Opts) -> Result<SegmentStream>
+    where
+        R: AsyncRead + Unpin + Send + 'static,
+    {
+        self.validate_opts(opts)?;
+
+        let (opts, guard) = with_cancel_guard(opts);
+        let scribble = Arc::clone(self);
+        let input = DecodeInput::Stream(Box::new(SyncIoBridge::new(r)));
+        let (tx, rx) = mpsc::channel(SEGMENT_QUEUE_LEN);
+
+        let handle = tokio::task::spawn_blocking(move || {
+            let mut encoder = ChannelEncoder { tx: tx.clone() };
+            if let Err(err) = scribble.transcribe_input_with_encoder(input, &opts, &mut encoder) {
+                // Fails only if the stream was dropped, in which case nobody is listening.
+                let _ = tx.blocking_send(Err(err));
+            }
+        });
+
+        Ok(SegmentStream {
+            rx,
+            handle: Some(handle),
+            guard,
+        })
+    }
+}
+
+/// Async stream of segments returned by [`Scribble::transcribe_stream`].
+pub struct SegmentStream {
+    rx: mpsc::Receiver<Result<Segment>>,
+    handle: Option<JoinHandle<()>>,
+    guard: CancelGuard,
+}
+
+impl Stream for SegmentStream {
+    type Item = Result<Segment>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if let Some(item) = ready!(self.rx.poll_recv(cx)) {
+            return Poll::Ready(Some(item));
+        }
+
+        // The channel closed, so the transcription thread is done; surface a panic if it had one.
+        if let Some(handle) = self.handle.as_mut() {
+            let res = ready!(Pin::new(handle).poll(cx));
+            self.handle = None;
+            self.guard.disarm();
+            if let Err(err) = res
+                && err.is_panic()
+            {
+                std::panic::resume_unwind(err.into_panic());
+            }
+        }
+        Poll::Ready(None)
+    }
+}
+
+/// Forwards segments to a [`SegmentStream`] from the transcription thread.
+struct ChannelEncoder {
+    tx: mpsc::Sender<Result<Segment>>,
+}
+
+impl SegmentEncoder for ChannelEncoder {
+    fn write_segment(&mut self, seg: &Segment) -> Result<()> {
+        // The receiver is gone only when the stream was dropped.
+        self.tx
+            .blocking_send(Ok(seg.clone()))
+            .map_err(|_| crate::Error::Cancelled)
+    }
+
+    fn close(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Cancels a transcription when dropped unless disarmed first.
+struct CancelGuard(Option<CancellationToken>);
+
+impl CancelGuard {
+    fn disarm(&mut self) {
+        self.0 = None;
+    }
+}
+
+impl Drop for CancelGuard {
+    fn drop(&mut self) {
+        if let Some(token) = self.0.take() {
+            token.cancel();
+        }
+    }
+}
+
+/// Clone `opts` with a cancellation token (reusing the caller's) and a guard that triggers it.
+fn with_cancel_guard(opts: &Opts) -> (Opts, CancelGuard) {
+    let mut opts = opts.clone();
+    let token = opts
+        .cancellation_token
+        .get_or_insert_with(CancellationToken::new)
+        .clone();
+    (opts, CancelGuard(Some(token)))
+}
+
+async fn join(handle: JoinHandle<Result<()>>) -> Result<()> {
+    match handle.await {
+        Ok(res) => res,
+        Err(err) if err.is_panic() => std::panic::resume_unwind(err.into_panic()),
+        // The runtime is shutting down.
+        Err(_) => Err(crate::Error::Cancelled),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::StreamExt;
+
+    use super::*;
+    use crate::audio_pipeline::TARGET_SAMPLE_RATE;
+    use crate::backend::BackendStream;
+    use crate::output_type::OutputType;
+
+    /// Emits one segment per second of audio it is fed.
+    struct PerSecondBackend;
+
+    struct PerSecondStream<'a> {
+        encoder: &'a mut dyn SegmentEncoder,
+        samples_seen: usize,
+        emitted: usize,
+    }
+
+    impl BackendStream for PerSecondStream<'_> {
+        fn on_samples(&mut self, samples_16k_mono: &[f32]) -> Result<bool> {
+            self.samples_seen += samples_16k_mono.len();
+            while (self.emitted + 1) * TARGET_SAMPLE_RATE as usize <= self.samples_seen {
+                self.encoder.write_segment(&segment(self.emitted))?;
+                self.emitted += 1;
+            }
+            Ok(true)
+        }
+
+        fn finish(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Backend for PerSecondBackend {
+        type Stream<'a> = PerSecondStream<'a>;
+
+        fn transcribe_full(
+            &self,
+            opts: &Opts,
+            encoder: &mut dyn SegmentEncoder,
+            samples: &[f32],
+        ) -> Result<()> {
+            let mut stream = self.create_stream(opts, encoder)?;
+            stream.on_samples(samples)?;
+            stream.finish()
+        }
+
+        fn create_stream<'a>(
+            &'a self,
+            _opts: &'a Opts,
+            encoder: &'a mut dyn SegmentEncoder,
+        ) -> Result<Self::Stream<'a>> {
+            Ok(PerSecondStream {
+                encoder,
+                samples_seen: 0,
+                emitted: 0,
+            })
+        }
+    }
+
+    fn segment(second: usize) -> Segment {
+        Segment {
+            start_seconds: second as f32,
+            end_seconds: (second + 1) as f32,
+            text: format!("second {second}"),
+            confidence: 1.0,
+            ..Segment::default()
+        }
+    }
+
+    fn opts(output_type: OutputType) -> Opts {
+        Opts {
+            output_type,
+            ..Opts::default()
+        }
+    }
+
+    fn jfk() -> std::io::Cursor<Vec<u8>> {
+        std::io::Cursor::new(std::fs::read("tests/fixtures/jfk.wav").expect("read fixture"))
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn transcribe_async_writes_output() -> anyhow::Result<()> {
+        let scribble = Arc::new(Scribble::with_backend(PerSecondBackend));
+        let (out_tx, mut out_rx) = tokio::io::duplex(64 * 1024);
+
+        let reader = tokio::spawn(async move {
+            let mut out = Vec::new();
+            tokio::io::AsyncReadExt::read_to_end(&mut out_rx, &mut out).await?;
+            anyhow::Ok(out)
+        });
+        scribble
+            .transcribe_async(jfk(), out_tx, &opts(OutputType::Json))
+            .await?;
+
+        let parsed: serde_json::Value = serde_json::from_slice(&reader.await??)?;
+        // jfk.wav is 8.5s long.
+        assert_eq!(parsed.as_array().map(Vec::len), Some(8));
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn transcribe_seekable_async_decodes_trailing_moov() -> anyhow::Result<()> {
+        let scribble = Arc::new(Scribble::with_backend(PerSecondBackend));
+        let (out_tx, mut out_rx) = tokio::io::duplex(64 * 1024);
+
+        let reader = tokio::spawn(async move {
+            let mut out = Vec::new();
+            tokio::io::AsyncReadExt::read_to_end(&mut out_rx, &mut out).await?;
+            anyhow::Ok(out)
+        });
+        let input = std::fs::File::open("tests/fixtures/tone-moov-at-end.mp4")?;
+        scribble
+            .transcribe_seekable_async(input, out_tx, &opts(OutputType::Json))
+            .await?;
+
+        let parsed: serde_json::Value = serde_json::from_slice(&reader.await??)?;
+        assert_eq!(parsed.as_array().map(Vec::len), Some(2));
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn transcribe_stream_yields_segments_in_order() -> anyhow::Result<()> {
+        let scribble = Arc::new(Scribble::with_backend(PerSecondBackend));
+
+        let segments: Vec<Segment> = scribble
+            .transcribe_stream(jfk(), &opts(OutputType::Json))?
+            .map(|segment| segment.expect("segment"))
+            .collect()
+            .await;
+
+        assert_eq!(segments.len(), 8);
+        assert_eq!(segments[3].text, "second 3");
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn dropping_stream_cancels_transcription() -> anyhow::Result<()> {
+        let scribble = Arc::new(Scribble::with_backend(PerSecondBackend));
+        let token = CancellationToken::new();
+        let opts = Opts {
+            cancellation_token: Some(token.clone()),
+            ..opts(OutputType::Json)
+        };
+
+        let mut stream = scribble.transcribe_stream(jfk(), &opts)?;
+        assert!(stream.next().await.is_some());
+        drop(stream);
+
+        assert!(token.is_cancelled());
+        Ok(())
+    }
+}
@@ -1,8 +1,8 @@
 //! Audio normalization pipeline for Scribble.
 //!
 //! Responsibilities:
-//! - Convert Symphonia-decoded PCM into interleaved `f32`
-//! - Downmix to mono
+//! - Convert Symphonia-decoded PCM into interleaved `f32` (raw PCM input arrives that way)
+//! - Downmix to mono (or pick a single channel)
 //! - Resample to Scribble’s target sample rate (when needed)
 //! - Emit fixed-size chunks via a callback (incremental consumption)
 //!
@@ -37,6 +37,9 @@ pub struct AudioPipeline {
 
     // Reusable mono output buffer for rubato (one channel).
     resample_out: Vec<Vec<f32>>,
+
+    // Source channel to keep, or `None` to downmix all channels.
+    channel: Option<usize>,
 }
 
 /// Creates an empty audio pipeline with no buffered samples or initialized resampler.
@@ -55,50 +58,81 @@ impl AudioPipeline {
             mono_src_acc: Vec::new(),
             resample_in: vec![Vec::new()],
             resample_out: vec![Vec::new()],
+            channel: None,
+        }
+    }
+
+    /// Create a pipeline that keeps only source channel `channel` (zero-based) instead of
+    /// downmixing, e.g. to transcribe each side of a stereo call recording separately.
+    pub fn for_channel(channel: usize) -> Self {
+        Self {
+            channel: Some(channel),
+            ..Self::new()
         }
     }
 
     /// Push a decoded Symphonia buffer through the pipeline and emit 16 kHz mono chunks.
     ///
     /// The `emit` callback receives mono 16 kHz `f32` samples.
-    /// Returning `Ok(false)` signals “stop early”.
+    /// Returning `Ok(false)` signals “stop early”; this method then returns `Ok(false)` too.
     pub fn push_decoded_and_emit(
         &mut self,
         decoded: &AudioBufferRef<'_>,
         target_chunk_frames: usize,
-        mut emit: impl FnMut(&[f32]) -> Result<bool>,
-    ) -> Result<()> {
+        emit: impl FnMut(&[f32]) -> Result<bool>,
+    ) -> Result<bool> {
         let (interleaved, src_rate, channels) =
             decoded_to_interleaved_f32(decoded, &mut self.sample_buf_f32)?;
+        self.push_interleaved_and_emit(&interleaved, src_rate, channels, target_chunk_frames, emit)
+    }
 
-        let mono_src = downmix_to_mono(&interleaved, channels);
+    /// Push interleaved `f32` samples (e.g. from raw PCM input) through the pipeline and emit
+    /// 16 kHz mono chunks.
+    ///
+    /// Same contract as [`AudioPipeline::push_decoded_and_emit`]; `interleaved` must hold whole
+    /// frames of `channels` samples at `src_rate`.
+    pub fn push_interleaved_and_emit(
+        &mut self,
+        interleaved: &[f32],
+        src_rate: u32,
+        channels: usize,
+        target_chunk_frames: usize,
+        mut emit: impl FnMut(&[f32]) -> Result<bool>,
+    ) -> Result<bool> {
+        if channels == 0 {
+            bail!("audio has zero channels");
+        }
+
+        let mono_src = match self.channel {
+            None => downmix_to_mono(interleaved, channels),
+            Some(channel) => extract_channel(interleaved, channels, channel)?,
+        };
 
         // Fast path: already at the target sample rate.
         if src_rate == TARGET_SAMPLE_RATE {
-            emit_mono_chunks(&mono_src, target_chunk_frames, &mut emit)?;
-            return Ok(());
+            return emit_mono_chunks(&mono_src, target_chunk_frames, &mut emit);
         }
 
         // Slow path: resample to the target sample rate.
         self.ensure_resampler(src_rate)?;
-        self.push_and_flush_resampler(&mono_src, target_chunk_frames, &mut emit)?;
-        Ok(())
+        self.push_and_flush_resampler(&mono_src, target_chunk_frames, &mut emit)
     }
 
     /// Flush remaining buffered samples at end-of-stream.
     ///
-    /// If resampling was never needed, this is a no-op.
+    /// If resampling was never needed, this is a no-op. Returns `Ok(false)` if `emit` asked to
+    /// stop early.
     pub fn finalize(
         &mut self,
         target_chunk_frames: usize,
         mut emit: impl FnMut(&[f32]) -> Result<bool>,
-    ) -> Result<()> {
+    ) -> Result<bool> {
         let Some(rs) = self.resampler.as_mut() else {
-            return Ok(());
+            return Ok(true);
         };
 
         if self.mono_src_acc.is_empty() {
-            return Ok(());
+            return Ok(true);
         }
 
         // rubato expects exact block sizes; pad the remainder with zeros.
@@ -114,10 +148,12 @@ impl AudioPipeline {
             let block: Vec<f32> = self.mono_src_acc.drain(..in_next).collect();
 
             let out = self.resample_block_into_out(&block)?;
-            emit_mono_chunks(out, target_chunk_frames, &mut emit)?;
+            if !emit_mono_chunks(out, target_chunk_frames, &mut emit)? {
+                return Ok(false);
+            }
         }
 
-        Ok(())
+        Ok(true)
     }
 
     fn ensure_resampler(&mut self, src_rate: u32) -> Result<()> {
@@ -160,7 +196,7 @@ impl AudioPipeline {
         mono_src: &[f32],
         target_chunk_frames: usize,
         emit: &mut impl FnMut(&[f32]) -> Result<bool>,
-    ) -> Result<()> {
+    ) -> Result<bool> {
         self.mono_src_acc.extend_from_slice(mono_src);
 
         loop {
@@ -179,14 +215,12 @@ impl AudioPipeline {
 
             // `out` is a borrowed view into `self.resample_out`.
             // Emit in fixed-size chunks.
-            for chunk in out.chunks(target_chunk_frames) {
-                if !emit(chunk)? {
-                    return Ok(());
-                }
+            if !emit_mono_chunks(out, target_chunk_frames, emit)? {
+                return Ok(false);
             }
         }
 
-        Ok(())
+        Ok(true)
     }
 
     /// Resample one mono block and return a borrowed view of the mono output at the target sample rate.
@@ -223,6 +257,82 @@ impl AudioPipeline {
     }
 }
 
+/// How far (target-rate frames) one track may run ahead of another before the lagging one is
+/// treated as silent. Bounds buffering when a track ends early or has gaps; audio that arrives
+/// for the silenced stretch afterwards is dropped so the track stays aligned.
+const MAX_TRACK_LAG_FRAMES: usize = 10 * TARGET_SAMPLE_RATE as usize;
+
+/// Mixes the normalized output of several tracks into one mono stream.
+///
+/// Each track's 16 kHz mono samples are buffered until every track has reached the same point,
+/// then averaged (the same policy as the channel downmix) and emitted in fixed-size chunks.
+pub struct TrackMixer {
+    pending: Vec<Vec<f32>>,
+    /// Per track, samples already mixed in as silence that must be dropped when they arrive.
+    skip: Vec<usize>,
+}
+
+impl TrackMixer {
+    /// Create a mixer for `tracks` tracks.
+    pub fn new(tracks: usize) -> Self {
+        Self {
+            pending: vec![Vec::new(); tracks],
+            skip: vec![0; tracks],
+        }
+    }
+
+    /// Buffer 16 kHz mono samples for the track at `track`.
+    pub fn push(&mut self, track: usize, mono_16k: &[f32]) {
+        let skipped = self.skip[track].min(mono_16k.len());
+        self.skip[track] -= skipped;
+        self.pending[track].extend_from_slice(&mono_16k[skipped..]);
+    }
+
+    /// Emit the mixed samples every track has reached, in chunks of `target_chunk_frames`.
+    ///
+    /// With `flush` set (end of stream), everything buffered is emitted and shorter tracks are
+    /// padded with silence. Returns `Ok(false)` as soon as `emit` asks to stop.
+    pub fn emit_mixed(
+        &mut self,
+        target_chunk_frames: usize,
+        flush: bool,
+        mut emit: impl FnMut(&[f32]) -> Result<bool>,
+    ) -> Result<bool> {
+        let longest = self.pending.iter().map(Vec::len).max().unwrap_or(0);
+        let shortest = self.pending.iter().map(Vec::len).min().unwrap_or(0);
+
+        let ready = if flush {
+            longest
+        } else {
+            // Keep partial chunks buffered so chunk sizes match the single-track path.
+            let ready = shortest.max(longest.saturating_sub(MAX_TRACK_LAG_FRAMES));
+            ready - ready % target_chunk_frames.max(1)
+        };
+        if ready == 0 {
+            return Ok(true);
+        }
+
+        let mut mixed = vec![0.0; ready];
+        for (track, skip) in self.pending.iter_mut().zip(&mut self.skip) {
+            let take = ready.min(track.len());
+            for (out, sample) in mixed.iter_mut().zip(track.drain(..take)) {
+                *out += sample;
+            }
+            if !flush {
+                // A lagging track was padded with silence up to `ready`; its late samples for
+                // that stretch belong to time that has already been emitted.
+                *skip += ready - take;
+            }
+        }
+        let tracks = self.pending.len() as f32;
+        for sample in &mut mixed {
+            *sample /= tracks;
+        }
+
+        emit_mono_chunks(&mixed, target_chunk_frames, &mut emit)
+    }
+}
+
 fn decoded_to_interleaved_f32(
     decoded: &AudioBufferRef<'_>,
     sample_buf_f32: &mut Option<SampleBuffer<f32>>,
@@ -281,18 +391,34 @@ fn downmix_to_mono(interleaved: &[f32], channels: usize) -> Vec<f32> {
     mono
 }
 
+/// Keep only `channel` of interleaved samples.
+fn extract_channel(interleaved: &[f32], channels: usize, channel: usize) -> Result<Vec<f32>> {
+    if channel >= channels {
+        bail!("channel {channel} requested, but the audio has {channels} channel(s)");
+    }
+
+    Ok(interleaved
+        .iter()
+        .skip(channel)
+        .step_by(channels)
+        .copied()
+        .collect())
+}
+
 /// Emit mono 16 kHz samples to the callback in fixed-size chunks.
+///
+/// Returns `Ok(false)` as soon as the callback asks to stop.
 fn emit_mono_chunks(
     mono_16k: &[f32],
     chunk_frames: usize,
     emit: &mut impl FnMut(&[f32]) -> Result<bool>,
-) -> Result<()> {
+) -> Result<bool> {
     for chunk in mono_16k.chunks(chunk_frames) {
         if !emit(chunk)? {
-            break;
+            return Ok(false);
         }
     }
-    Ok(())
+    Ok(true)
 }
 
 #[cfg(test)]
@@ -325,11 +451,12 @@ mod tests {
     fn emit_mono_chunks_respects_early_stop() -> anyhow::Result<()> {
         let mut seen = Vec::new();
         let mono = vec![1.0; 10];
-        emit_mono_chunks(&mono, 4, &mut |chunk| {
+        let completed = emit_mono_chunks(&mono, 4, &mut |chunk| {
             seen.push(chunk.len());
             Ok(false)
         })?;
 
+        assert!(!completed);
         assert_eq!(seen, vec![4]);
         Ok(())
     }
@@ -373,4 +500,64 @@ mod tests {
         assert!(emitted_samples > 0);
         Ok(())
     }
+
+    #[test]
+    fn track_mixer_waits_for_every_track_and_averages() -> Result<()> {
+        let mut mixer = TrackMixer::new(2);
+        let mut out = Vec::new();
+
+        mixer.push(0, &[1.0; 4]);
+        assert!(mixer.emit_mixed(2, false, |chunk| {
+            out.extend_from_slice(chunk);
+            Ok(true)
+        })?);
+        assert!(out.is_empty());
+
+        mixer.push(1, &[0.0; 3]);
+        mixer.emit_mixed(2, false, |chunk| {
+            out.extend_from_slice(chunk);
+            Ok(true)
+        })?;
+        assert_eq!(out, [0.5, 0.5]);
+
+        mixer.emit_mixed(2, true, |chunk| {
+            out.extend_from_slice(chunk);
+            Ok(true)
+        })?;
+        assert_eq!(out, [0.5, 0.5, 0.5, 0.5]);
+        Ok(())
+    }
+
+    #[test]
+    fn track_mixer_keeps_a_late_track_aligned() -> Result<()> {
+        let mut mixer = TrackMixer::new(2);
+        let mut out = Vec::new();
+        let mut collect = |chunk: &[f32]| {
+            out.extend_from_slice(chunk);
+            Ok(true)
+        };
+
+        // Track 0 runs more than 10 s ahead, so track 1 is treated as silent up to 1 s.
+        mixer.push(0, &vec![1.0; MAX_TRACK_LAG_FRAMES + 16_000]);
+        mixer.emit_mixed(16_000, false, &mut collect)?;
+        // Track 1's first second arrives late and is dropped; its second second lines up with
+        // track 0's.
+        mixer.push(1, &[0.0; 16_000]);
+        mixer.push(1, &[1.0; 16_000]);
+        mixer.emit_mixed(16_000, true, &mut collect)?;
+
+        assert_eq!(out.len(), MAX_TRACK_LAG_FRAMES + 16_000);
+        assert!(out[..16_000].iter().all(|&s| s == 0.5));
+        assert!(out[16_000..32_000].iter().all(|&s| s == 1.0));
+        assert!(out[32_000..].iter().all(|&s| s == 0.5));
+        Ok(())
+    }
+
+    #[test]
+    fn extract_channel_keeps_one_channel() -> Result<()> {
+        let interleaved = [0.1, 0.9, 0.2, 0.8];
+        assert_eq!(extract_channel(&interleaved, 2, 1)?, [0.9, 0.8]);
+        assert!(extract_channel(&interleaved, 2, 2).is_err());
+        Ok(())
+    }
 }
@@ -1,6 +1,10 @@
+use std::ops::Range;
+
 use crate::Result;
+use crate::audio_pipeline::TARGET_SAMPLE_RATE;
+use crate::capabilities::Capabilities;
 use crate::opts::Opts;
-use crate::segment_encoder::SegmentEncoder;
+use crate::segment_encoder::{OffsetEncoder, SegmentEncoder};
 
 /// Pluggable ASR backend used by [`crate::Scribble`].
 ///
@@ -30,6 +34,32 @@ pub trait Backend {
         samples: &[f32],
     ) -> Result<()>;
 
+    /// Transcribe independent chunks of a contiguous sample buffer, emitting segments in chunk
+    /// order with timestamps relative to the start of `samples`.
+    ///
+    /// `chunks` are sorted, non-overlapping sample ranges into `samples`. The default
+    /// implementation runs [`Backend::transcribe_full`] on each chunk in turn; backends that can
+    /// run several inference passes at once may override it to transcribe chunks concurrently.
+    ///
+    /// Backends should not call `encoder.close()`; the caller is responsible for encoder lifecycle.
+    fn transcribe_chunks(
+        &self,
+        opts: &Opts,
+        encoder: &mut dyn SegmentEncoder,
+        samples: &[f32],
+        chunks: &[Range<usize>],
+    ) -> Result<()> {
+        for chunk in chunks {
+            if opts.is_cancelled() {
+                return Err(crate::Error::Cancelled);
+            }
+            let offset_seconds = chunk.start as f32 / TARGET_SAMPLE_RATE as f32;
+            let mut encoder = OffsetEncoder::new(encoder, offset_seconds);
+            self.transcribe_full(opts, &mut encoder, &samples[chunk.clone()])?;
+        }
+        Ok(())
+    }
+
     /// Create a streaming transcriber that accepts samples incrementally.
     ///
     /// Backends should not call `encoder.close()`; the caller is responsible for encoder lifecycle.
@@ -38,6 +68,16 @@ pub trait Backend {
         opts: &'a Opts,
         encoder: &'a mut dyn SegmentEncoder,
     ) -> Result<Self::Stream<'a>>;
+
+    /// Describe what the model selected by `model_key` supports (`None` selects the default
+    /// model).
+    ///
+    /// [`crate::Scribble`] validates [`Opts`] against this before transcribing. The default
+    /// implementation claims every feature and any language.
+    fn capabilities(&self, model_key: Option<&str>) -> Capabilities {
+        let _ = model_key;
+        Capabilities::default()
+    }
 }
 
 /// Streaming transcription interface returned by [`Backend::create_stream`].
@@ -50,3 +90,84 @@ pub trait BackendStream {
     /// Flush and emit any final segments.
     fn finish(&mut self) -> Result<()>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::segments::Segment;
+
+    /// Emits one segment spanning the whole buffer it is given.
+    struct WholeBufferBackend;
+
+    struct NoStream;
+
+    impl BackendStream for NoStream {
+        fn on_samples(&mut self, _samples_16k_mono: &[f32]) -> Result<bool> {
+            Ok(true)
+        }
+
+        fn finish(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Backend for WholeBufferBackend {
+        type Stream<'a> = NoStream;
+
+        fn transcribe_full(
+            &self,
+            _opts: &Opts,
+            encoder: &mut dyn SegmentEncoder,
+            samples: &[f32],
+        ) -> Result<()> {
+            encoder.write_segment(&Segment {
+                start_seconds: 0.0,
+                end_seconds: samples.len() as f32 / TARGET_SAMPLE_RATE as f32,
+                text: format!("{} samples", samples.len()),
+                ..Segment::default()
+            })
+        }
+
+        fn create_stream<'a>(
+            &'a self,
+            _opts: &'a Opts,
+            _encoder: &'a mut dyn SegmentEncoder,
+        ) -> Result<Self::Stream<'a>> {
+            Ok(NoStream)
+        }
+    }
+
+    #[derive(Default)]
+    struct CollectingEncoder {
+        segments: Vec<Segment>,
+    }
+
+    impl SegmentEncoder for CollectingEncoder {
+        fn write_segment(&mut self, seg: &Segment) -> Result<()> {
+            self.segments.push(seg.clone());
+            Ok(())
+        }
+
+        fn close(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn transcribe_chunks_default_offsets_each_chunk() -> Result<()> {
+        let rate = TARGET_SAMPLE_RATE as usize;
+        let samples = vec![0.0; 5 * rate];
+        let chunks = [0..2 * rate, 2 * rate..5 * rate];
+        let mut encoder = CollectingEncoder::default();
+
+        WholeBufferBackend.transcribe_chunks(&Opts::default(), &mut encoder, &samples, &chunks)?;
+
+        let spans: Vec<(f32, f32)> = encoder
+            .segments
+            .iter()
+            .map(|s| (s.start_seconds, s.end_seconds))
+            .collect();
+        assert_eq!(spans, vec![(0.0, 2.0), (2.0, 5.0)]);
+        Ok(())
+    }
+}
@@ -0,0 +1,593 @@
+//! Backend combinator that retries failed work on a secondary backend.
+
+use std::ops::Range;
+
+use crate::Result;
+use crate::audio_pipeline::TARGET_SAMPLE_RATE;
+use crate::backend::{Backend, BackendStream};
+use crate::capabilities::Capabilities;
+use crate::opts::Opts;
+use crate::segment_encoder::{OffsetEncoder, SegmentEncoder};
+use crate::segments::Segment;
+
+/// Audio transcribed per attempt when streaming; matches Whisper's native window.
+const DEFAULT_CHUNK_SECONDS: usize = 30;
+
+/// How far back from a full chunk's end (at most half the chunk) a stream looks for a quiet
+/// place to cut.
+const CUT_SEARCH_SECONDS: usize = 5;
+
+/// Frame length (20 ms at the target rate) used to find the quietest cut point.
+const CUT_FRAME_SAMPLES: usize = TARGET_SAMPLE_RATE as usize / 50;
+
+/// What a [`FallbackBackend`] stream or chunked transcription does after the primary backend has
+/// failed once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FallbackPolicy {
+    /// Use the secondary backend for the rest of the stream.
+    ///
+    /// Suits failures that will not fix themselves, such as a GPU running out of memory.
+    SwitchForRestOfStream,
+
+    /// Try the primary backend again for every chunk, falling back chunk by chunk.
+    RetryPrimaryEachChunk,
+}
+
+/// A [`Backend`] that runs `primary` and, if it fails, retries the same audio on `secondary`.
+///
+/// Typical use is a GPU model backed by a CPU model. Segments produced by the secondary have
+/// [`Segment::from_fallback`] set. Cancellation is never retried.
+///
+/// Primary output is held back until the attempt succeeds, so a failure part-way through never
+/// leaves duplicated or half-finished output:
+/// - Full-buffer transcription retries the whole buffer.
+/// - Chunked transcription attempts each chunk on its own, one after another, so a failed chunk
+///   only sends that chunk to the secondary. What happens after the first failure is set by
+///   [`FallbackPolicy`].
+/// - Streaming transcribes chunks of about [`FallbackBackend::with_chunk_seconds`] instead of
+///   using the inner backends' own streams, so each chunk can be retried on its own. What
+///   happens after the first failure is set by [`FallbackPolicy`].
+///
+/// Streaming costs some accuracy compared with the inner backends' own streams: each chunk is
+/// transcribed without the text before it, and although chunks are cut at the quietest point of
+/// their last few seconds, continuous speech can still be cut mid-word.
+pub struct FallbackBackend<P, S> {
+    primary: P,
+    secondary: S,
+    policy: FallbackPolicy,
+    chunk_seconds: usize,
+}
+
+impl<P: Backend, S: Backend> FallbackBackend<P, S> {
+    /// Wrap `primary` with `secondary` as its fallback.
+    ///
+    /// Streams switch to the secondary for good after the first failure; see
+    /// [`FallbackBackend::with_policy`].
+    pub fn new(primary: P, secondary: S) -> Self {
+        Self {
+            primary,
+            secondary,
+            policy: FallbackPolicy::SwitchForRestOfStream,
+            chunk_seconds: DEFAULT_CHUNK_SECONDS,
+        }
+    }
+
+    /// Set what streams do after the primary has failed once.
+    pub fn with_policy(mut self, policy: FallbackPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Seconds of audio per attempt when streaming. Defaults to 30; values below 1 are raised
+    /// to 1.
+    ///
+    /// Chunks are cut at the quietest 20 ms within their last 5 seconds (or last half, for
+    /// short chunks), so most attempts are slightly shorter than this.
+    pub fn with_chunk_seconds(mut self, chunk_seconds: usize) -> Self {
+        self.chunk_seconds = chunk_seconds.max(1);
+        self
+    }
+
+    /// The primary backend.
+    pub fn primary(&self) -> &P {
+        &self.primary
+    }
+
+    /// The secondary (fallback) backend.
+    pub fn secondary(&self) -> &S {
+        &self.secondary
+    }
+
+    /// Run `primary` into a buffer and flush it on success; otherwise run `secondary` straight
+    /// into `encoder`.
+    ///
+    /// Returns whether the primary failed (or was skipped).
+    fn attempt(
+        &self,
+        encoder: &mut dyn SegmentEncoder,
+        skip_primary: bool,
+        primary: impl FnOnce(&mut dyn SegmentEncoder) -> Result<()>,
+        secondary: impl FnOnce(&mut dyn SegmentEncoder) -> Result<()>,
+    ) -> Result<bool> {
+        if !skip_primary {
+            let mut held = HeldSegments::default();
+            match primary(&mut held) {
+                Ok(()) => {
+                    for segment in &held.0 {
+                        encoder.write_segment(segment)?;
+                    }
+                    return Ok(false);
+                }
+                Err(crate::Error::Cancelled) => return Err(crate::Error::Cancelled),
+                Err(err) => {
+                    tracing::warn!(error = %err, "primary backend failed; retrying on fallback");
+                }
+            }
+        }
+
+        secondary(&mut MarkFallback { inner: encoder })?;
+        Ok(true)
+    }
+}
+
+impl<P: Backend, S: Backend> Backend for FallbackBackend<P, S> {
+    type Stream<'a>
+        = FallbackStream<'a, P, S>
+    where
+        Self: 'a;
+
+    fn transcribe_full(
+        &self,
+        opts: &Opts,
+        encoder: &mut dyn SegmentEncoder,
+        samples: &[f32],
+    ) -> Result<()> {
+        self.attempt(
+            encoder,
+            false,
+            |held| self.primary.transcribe_full(opts, held, samples),
+            |encoder| self.secondary.transcribe_full(opts, encoder, samples),
+        )?;
+        Ok(())
+    }
+
+    fn transcribe_chunks(
+        &self,
+        opts: &Opts,
+        encoder: &mut dyn SegmentEncoder,
+        samples: &[f32],
+        chunks: &[Range<usize>],
+    ) -> Result<()> {
+        let mut primary_failed = false;
+        for chunk in chunks {
+            if opts.is_cancelled() {
+                return Err(crate::Error::Cancelled);
+            }
+            let samples = &samples[chunk.clone()];
+            let offset_seconds = chunk.start as f32 / TARGET_SAMPLE_RATE as f32;
+            let mut encoder = OffsetEncoder::new(encoder, offset_seconds);
+            let skip_primary =
+                primary_failed && self.policy == FallbackPolicy::SwitchForRestOfStream;
+
+            primary_failed |= self.attempt(
+                &mut encoder,
+                skip_primary,
+                |held| self.primary.transcribe_full(opts, held, samples),
+                |encoder| self.secondary.transcribe_full(opts, encoder, samples),
+            )?;
+        }
+        Ok(())
+    }
+
+    fn create_stream<'a>(
+        &'a self,
+        opts: &'a Opts,
+        encoder: &'a mut dyn SegmentEncoder,
+    ) -> Result<Self::Stream<'a>> {
+        Ok(FallbackStream {
+            backend: self,
+            opts,
+            encoder,
+            buffer: Vec::new(),
+            samples_done: 0,
+            primary_failed: false,
+        })
+    }
+
+    /// Only what both backends support, since either may end up serving a request. Streaming is
+    /// always supported: streams are built from full-buffer passes on the inner backends.
+    fn capabilities(&self, model_key: Option<&str>) -> Capabilities {
+        Capabilities {
+            streaming: true,
+            ..self
+                .primary
+                .capabilities(model_key)
+                .intersect(&self.secondary.capabilities(model_key))
+        }
+    }
+}
+
+/// Streaming state for [`FallbackBackend`]: buffers audio and transcribes it a chunk at a time.
+pub struct FallbackStream<'a, P, S> {
+    backend: &'a FallbackBackend<P, S>,
+    opts: &'a Opts,
+    encoder: &'a mut dyn SegmentEncoder,
+    buffer: Vec<f32>,
+    samples_done: usize,
+    primary_failed: bool,
+}
+
+impl<P: Backend, S: Backend> FallbackStream<'_, P, S> {
+    /// Transcribe the first `len` buffered samples and drop them from the buffer.
+    fn transcribe_prefix(&mut self, len: usize) -> Result<()> {
+        if len == 0 {
+            return Ok(());
+        }
+        if self.opts.is_cancelled() {
+            return Err(crate::Error::Cancelled);
+        }
+
+        let backend = self.backend;
+        let opts = self.opts;
+        let samples = &self.buffer[..len];
+        let offset_seconds = self.samples_done as f32 / TARGET_SAMPLE_RATE as f32;
+        let mut encoder = OffsetEncoder::new(self.encoder, offset_seconds);
+        let skip_primary =
+            self.primary_failed && backend.policy == FallbackPolicy::SwitchForRestOfStream;
+
+        let failed = backend.attempt(
+            &mut encoder,
+            skip_primary,
+            |held| backend.primary.transcribe_full(opts, held, samples),
+            |encoder| backend.secondary.transcribe_full(opts, encoder, samples),
+        )?;
+        self.primary_failed |= failed;
+
+        self.samples_done += len;
+        self.buffer.drain(..len);
+        Ok(())
+    }
+}
+
+impl<P: Backend, S: Backend> BackendStream for FallbackStream<'_, P, S> {
+    fn on_samples(&mut self, samples_16k_mono: &[f32]) -> Result<bool> {
+        let chunk_samples = self
+            .backend
+            .chunk_seconds
+            .saturating_mul(TARGET_SAMPLE_RATE as usize);
+        let mut rest = samples_16k_mono;
+        while !rest.is_empty() {
+            let take = (chunk_samples - self.buffer.len()).min(rest.len());
+            let (now, later) = rest.split_at(take);
+            self.buffer.extend_from_slice(now);
+            rest = later;
+            if self.buffer.len() == chunk_samples {
+                let search =
+                    (CUT_SEARCH_SECONDS * TARGET_SAMPLE_RATE as usize).min(chunk_samples / 2);
+                let cut = quietest_cut(&self.buffer, search);
+                self.transcribe_prefix(cut)?;
+            }
+        }
+        Ok(true)
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        self.transcribe_prefix(self.buffer.len())
+    }
+}
+
+/// Where to cut `samples`: the end of its quietest frame within the last `search` samples.
+///
+/// Ties go to the later frame, so audio with no quieter spot is cut at its very end.
+fn quietest_cut(samples: &[f32], search: usize) -> usize {
+    let end = samples.len();
+    let start = end.saturating_sub(search);
+    let mut best = (f32::INFINITY, end);
+    let mut frame_end = end;
+    while frame_end >= start + CUT_FRAME_SAMPLES {
+        let energy: f32 = samples[frame_end - CUT_FRAME_SAMPLES..frame_end]
+            .iter()
+            .map(|sample| sample * sample)
+            .sum();
+        if energy < best.0 {
+            best = (energy, frame_end);
+        }
+        frame_end -= CUT_FRAME_SAMPLES;
+    }
+    best.1
+}
+
+/// Holds the primary's segments until its attempt is known to have succeeded.
+#[derive(Default)]
+struct HeldSegments(Vec<Segment>);
+
+impl SegmentEncoder for HeldSegments {
+    fn write_segment(&mut self, seg: &Segment) -> Result<()> {
+        self.0.push(seg.clone());
+        Ok(())
+    }
+
+    fn close(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Sets [`Segment::from_fallback`] on everything the secondary emits.
+struct MarkFallback<'a> {
+    inner: &'a mut dyn SegmentEncoder,
+}
+
+impl SegmentEncoder for MarkFallback<'_> {
+    fn write_segment(&mut self, seg: &Segment) -> Result<()> {
+        let mut seg = seg.clone();
+        seg.from_fallback = true;
+        self.inner.write_segment(&seg)
+    }
+
+    fn write_partial(&mut self, segments: &[Segment]) -> Result<()> {
+        let segments: Vec<Segment> = segments
+            .iter()
+            .cloned()
+            .map(|mut seg| {
+                seg.from_fallback = true;
+                seg
+            })
+            .collect();
+        self.inner.write_partial(&segments)
+    }
+
+    /// The wrapped encoder's lifecycle belongs to the caller; closing the adapter is a no-op.
+    fn close(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    /// Emits one segment per buffer, or fails every call while `failing` is set.
+    struct TestBackend {
+        name: &'static str,
+        failing: Cell<bool>,
+        calls: Cell<usize>,
+    }
+
+    impl TestBackend {
+        fn new(name: &'static str, failing: bool) -> Self {
+            Self {
+                name,
+                failing: Cell::new(failing),
+                calls: Cell::new(0),
+            }
+        }
+    }
+
+    struct NoStream;
+
+    impl BackendStream for NoStream {
+        fn on_samples(&mut self, _samples_16k_mono: &[f32]) -> Result<bool> {
+            Ok(true)
+        }
+
+        fn finish(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Backend for TestBackend {
+        type Stream<'a> = NoStream;
+
+        fn transcribe_full(
+            &self,
+            _opts: &Opts,
+            encoder: &mut dyn SegmentEncoder,
+            samples: &[f32],
+        ) -> Result<()> {
+            self.calls.set(self.calls.get() + 1);
+            encoder.write_segment(&Segment {
+                start_seconds: 0.0,
+                end_seconds: samples.len() as f32 / TARGET_SAMPLE_RATE as f32,
+                text: self.name.to_owned(),
+                ..Segment::default()
+            })?;
+            if self.failing.get() {
+                return Err(crate::Error::Backend {
+                    message: format!("{} out of memory", self.name),
+                    source: None,
+                });
+            }
+            Ok(())
+        }
+
+        fn create_stream<'a>(
+            &'a self,
+            _opts: &'a Opts,
+            _encoder: &'a mut dyn SegmentEncoder,
+        ) -> Result<Self::Stream<'a>> {
+            Ok(NoStream)
+        }
+    }
+
+    #[derive(Default)]
+    struct Collect(Vec<Segment>);
+
+    impl SegmentEncoder for Collect {
+        fn write_segment(&mut self, seg: &Segment) -> Result<()> {
+            self.0.push(seg.clone());
+            Ok(())
+        }
+
+        fn close(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn summary(out: &Collect) -> Vec<(&str, f32, bool)> {
+        out.0
+            .iter()
+            .map(|s| (s.text.as_str(), s.start_seconds, s.from_fallback))
+            .collect()
+    }
+
+    #[test]
+    fn full_buffer_discards_failed_primary_output_and_marks_fallback() -> anyhow::Result<()> {
+        let backend = FallbackBackend::new(
+            TestBackend::new("gpu", true),
+            TestBackend::new("cpu", false),
+        );
+        let mut out = Collect::default();
+
+        backend.transcribe_full(&Opts::default(), &mut out, &[0.0; 16_000])?;
+
+        assert_eq!(summary(&out), [("cpu", 0.0, true)]);
+        Ok(())
+    }
+
+    #[test]
+    fn full_buffer_keeps_primary_output_on_success() -> anyhow::Result<()> {
+        let backend = FallbackBackend::new(
+            TestBackend::new("gpu", false),
+            TestBackend::new("cpu", false),
+        );
+        let mut out = Collect::default();
+
+        backend.transcribe_full(&Opts::default(), &mut out, &[0.0; 16_000])?;
+
+        assert_eq!(summary(&out), [("gpu", 0.0, false)]);
+        assert_eq!(backend.secondary().calls.get(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn chunks_fall_back_one_chunk_at_a_time() -> anyhow::Result<()> {
+        /// Fails only on buffers of exactly one second.
+        struct FailsOnOneSecond(TestBackend);
+
+        impl Backend for FailsOnOneSecond {
+            type Stream<'a> = NoStream;
+
+            fn transcribe_full(
+                &self,
+                opts: &Opts,
+                encoder: &mut dyn SegmentEncoder,
+                samples: &[f32],
+            ) -> Result<()> {
+                self.0.failing.set(samples.len() == 16_000);
+                self.0.transcribe_full(opts, encoder, samples)
+            }
+
+            fn create_stream<'a>(
+                &'a self,
+                _opts: &'a Opts,
+                _encoder: &'a mut dyn SegmentEncoder,
+            ) -> Result<Self::Stream<'a>> {
+                Ok(NoStream)
+            }
+        }
+
+        let backend = FallbackBackend::new(
+            FailsOnOneSecond(TestBackend::new("gpu", false)),
+            TestBackend::new("cpu", false),
+        )
+        .with_policy(FallbackPolicy::RetryPrimaryEachChunk);
+        let mut out = Collect::default();
+
+        backend.transcribe_chunks(
+            &Opts::default(),
+            &mut out,
+            &[0.0; 80_000],
+            &[0..32_000, 32_000..48_000, 48_000..80_000],
+        )?;
+
+        assert_eq!(
+            summary(&out),
+            [("gpu", 0.0, false), ("cpu", 2.0, true), ("gpu", 3.0, false)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn stream_switches_to_secondary_for_the_rest() -> anyhow::Result<()> {
+        let backend = FallbackBackend::new(
+            TestBackend::new("gpu", false),
+            TestBackend::new("cpu", false),
+        )
+        .with_chunk_seconds(1);
+        let opts = Opts::default();
+        let mut out = Collect::default();
+
+        let mut stream = backend.create_stream(&opts, &mut out)?;
+        stream.on_samples(&[0.0; 16_000])?;
+        backend.primary().failing.set(true);
+        stream.on_samples(&[0.0; 16_000])?;
+        backend.primary().failing.set(false);
+        stream.on_samples(&[0.0; 8_000])?;
+        stream.finish()?;
+
+        assert_eq!(
+            summary(&out),
+            [("gpu", 0.0, false), ("cpu", 1.0, true), ("cpu", 2.0, true)]
+        );
+        assert_eq!(backend.primary().calls.get(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn stream_can_retry_primary_each_chunk() -> anyhow::Result<()> {
+        let backend = FallbackBackend::new(
+            TestBackend::new("gpu", true),
+            TestBackend::new("cpu", false),
+        )
+        .with_chunk_seconds(1)
+        .with_policy(FallbackPolicy::RetryPrimaryEachChunk);
+        let opts = Opts::default();
+        let mut out = Collect::default();
+
+        let mut stream = backend.create_stream(&opts, &mut out)?;
+        stream.on_samples(&[0.0; 16_000])?;
+        backend.primary().failing.set(false);
+        stream.on_samples(&[0.0; 16_000])?;
+        stream.finish()?;
+
+        assert_eq!(summary(&out), [("cpu", 0.0, true), ("gpu", 1.0, false)]);
+        Ok(())
+    }
+
+    #[test]
+    fn stream_cuts_chunks_at_the_quietest_point() -> anyhow::Result<()> {
+        let backend = FallbackBackend::new(
+            TestBackend::new("gpu", false),
+            TestBackend::new("cpu", false),
+        )
+        .with_chunk_seconds(2);
+        let opts = Opts::default();
+        let mut out = Collect::default();
+
+        // Speech with a 100 ms pause at 1.5 s, inside the last half of the first chunk.
+        let mut samples = vec![0.5; 24_000];
+        samples.extend([0.0; 1_600]);
+        samples.extend([0.5; 14_400]);
+
+        let mut stream = backend.create_stream(&opts, &mut out)?;
+        stream.on_samples(&samples)?;
+        stream.finish()?;
+
+        assert_eq!(summary(&out), [("gpu", 0.0, false), ("gpu", 1.6, false)]);
+        Ok(())
+    }
+
+    #[test]
+    fn errors_from_both_backends_surface() {
+        let backend =
+            FallbackBackend::new(TestBackend::new("gpu", true), TestBackend::new("cpu", true));
+
+        let err = backend
+            .transcribe_full(&Opts::default(), &mut Collect::default(), &[0.0; 1_600])
+            .unwrap_err();
+
+        assert!(err.to_string().contains("cpu out of memory"));
+    }
+}
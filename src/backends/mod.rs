@@ -1,2 +1,17 @@
 /// Built-in ASR backends.
 pub mod whisper;
+
+/// Combinator that retries failed work on a secondary backend.
+pub mod fallback;
+
+/// Backend for OpenAI-compatible HTTP transcription services.
+#[cfg(feature = "openai")]
+pub mod openai;
+
+/// Backend that offloads inference to a remote `scribble-server`.
+#[cfg(feature = "remote")]
+pub mod remote;
+
+/// Scripted backend for tests that must run without a model.
+#[cfg(feature = "testing")]
+pub mod scripted;
@@ -0,0 +1,574 @@
+//! Backend that delegates inference to an OpenAI-compatible HTTP transcription service.
+
+use std::io::Cursor;
+use std::time::Duration;
+
+use anyhow::{Context, Result as AnyResult, bail};
+use reqwest::blocking::{Client, multipart};
+use serde::Deserialize;
+
+use crate::Result;
+use crate::audio_pipeline::TARGET_SAMPLE_RATE;
+use crate::backend::{Backend, BackendStream};
+use crate::opts::Opts;
+use crate::segment_encoder::{OffsetEncoder, SegmentEncoder};
+use crate::segments::Segment;
+use crate::token::{Token, TokenKind, Word};
+
+/// Model name sent unless [`OpenAiBackend::with_model`] sets another.
+const DEFAULT_MODEL: &str = "whisper-1";
+
+/// Audio sent per request; matches Whisper's native window.
+const DEFAULT_CHUNK_SECONDS: usize = 30;
+
+/// Per-request timeout. Each request carries at most one chunk, so this mostly covers a slow or
+/// busy service.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Backend that posts audio to an OpenAI-compatible `/v1/audio/transcriptions` endpoint.
+///
+/// Audio is encoded as 16-bit mono WAV at Scribble's target sample rate and sent with
+/// `response_format=verbose_json`; the returned segments and words are mapped onto
+/// [`Segment`]s. The model name is set once with [`OpenAiBackend::with_model`]; `Opts::model_key`
+/// names a local model file and is ignored here. `Opts::enable_translate_to_english` switches to
+/// the `/v1/audio/translations` endpoint.
+///
+/// Both modes send audio in fixed-size chunks, one request per chunk (see
+/// [`OpenAiBackend::with_chunk_seconds`]), which keeps each upload under the service's size
+/// limit (25 MB for OpenAI). Chunk boundaries are not aligned to silences, so a word may
+/// occasionally be split across two requests.
+pub struct OpenAiBackend {
+    client: Client,
+    base_url: String,
+    api_key: Option<String>,
+    model: String,
+    chunk_seconds: usize,
+}
+
+impl OpenAiBackend {
+    /// Create a backend for the service at `base_url` (e.g. `https://api.openai.com`).
+    ///
+    /// Requests go to `{base_url}/v1/audio/transcriptions`.
+    pub fn new(base_url: impl Into<String>) -> Result<Self> {
+        let client = Client::builder()
+            .user_agent(concat!("scribble/", env!("CARGO_PKG_VERSION")))
+            .timeout(DEFAULT_TIMEOUT)
+            .build()
+            .context("failed to build HTTP client")?;
+
+        Ok(Self {
+            client,
+            base_url: base_url.into().trim_end_matches('/').to_owned(),
+            api_key: None,
+            model: DEFAULT_MODEL.to_owned(),
+            chunk_seconds: DEFAULT_CHUNK_SECONDS,
+        })
+    }
+
+    /// Send `key` as a bearer token with every request.
+    pub fn with_api_key(mut self, key: impl Into<String>) -> Self {
+        self.api_key = Some(key.into());
+        self
+    }
+
+    /// Model name sent with every request. Defaults to `whisper-1`.
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        self
+    }
+
+    /// Seconds of audio sent per request. Defaults to 30; values below 1 are raised to 1.
+    ///
+    /// Each second is about 32 KB of WAV, so keep this under roughly 13 minutes for services
+    /// with a 25 MB upload limit.
+    pub fn with_chunk_seconds(mut self, chunk_seconds: usize) -> Self {
+        self.chunk_seconds = chunk_seconds.max(1);
+        self
+    }
+
+    /// Use a preconfigured HTTP client (for custom timeouts, proxies or TLS roots).
+    ///
+    /// The default client times out each request after 10 minutes.
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Transcribe one buffer and return its segments, timed relative to the buffer.
+    fn request_segments(&self, opts: &Opts, samples: &[f32]) -> AnyResult<Vec<Segment>> {
+        let endpoint = if opts.enable_translate_to_english {
+            "translations"
+        } else {
+            "transcriptions"
+        };
+        let url = format!("{}/v1/audio/{endpoint}", self.base_url);
+
+        let audio = multipart::Part::bytes(encode_wav(samples)?)
+            .file_name("audio.wav")
+            .mime_str("audio/wav")?;
+        let mut form = multipart::Form::new()
+            .part("file", audio)
+            .text("model", self.model.clone())
+            .text("response_format", "verbose_json")
+            .text("timestamp_granularities[]", "segment")
+            .text("timestamp_granularities[]", "word");
+        if let Some(language) = &opts.language {
+            form = form.text("language", language.clone());
+        }
+
+        let mut request = self.client.post(&url).multipart(form);
+        if let Some(key) = &self.api_key {
+            request = request.bearer_auth(key);
+        }
+
+        let response = request
+            .send()
+            .with_context(|| format!("request to {url} failed"))?;
+        let status = response.status();
+        let body = response
+            .text()
+            .with_context(|| format!("failed to read response from {url}"))?;
+        if !status.is_success() {
+            bail!("{url} returned {status}: {}", body.trim());
+        }
+
+        let parsed: VerboseTranscription = serde_json::from_str(&body)
+            .with_context(|| format!("unexpected response from {url}"))?;
+        Ok(parsed.into_segments(opts, samples.len()))
+    }
+
+    fn chunk_samples(&self) -> usize {
+        self.chunk_seconds
+            .saturating_mul(TARGET_SAMPLE_RATE as usize)
+    }
+
+    /// Transcribe one chunk starting `offset_seconds` into the input and write its segments.
+    fn emit(
+        &self,
+        opts: &Opts,
+        encoder: &mut dyn SegmentEncoder,
+        samples: &[f32],
+        offset_seconds: f32,
+    ) -> Result<()> {
+        if opts.is_cancelled() {
+            return Err(crate::Error::Cancelled);
+        }
+
+        let segments =
+            self.request_segments(opts, samples)
+                .map_err(|err| crate::Error::Backend {
+                    message: "remote transcription failed".to_owned(),
+                    source: Some(err.into()),
+                })?;
+
+        let mut encoder = OffsetEncoder::new(encoder, offset_seconds);
+        for segment in &segments {
+            encoder.write_segment(segment)?;
+        }
+        Ok(())
+    }
+}
+
+impl Backend for OpenAiBackend {
+    type Stream<'a> = OpenAiStream<'a>;
+
+    fn transcribe_full(
+        &self,
+        opts: &Opts,
+        encoder: &mut dyn SegmentEncoder,
+        samples: &[f32],
+    ) -> Result<()> {
+        let chunk_samples = self.chunk_samples();
+        for (index, chunk) in samples.chunks(chunk_samples).enumerate() {
+            let offset_seconds = (index * chunk_samples) as f32 / TARGET_SAMPLE_RATE as f32;
+            self.emit(opts, encoder, chunk, offset_seconds)?;
+        }
+        Ok(())
+    }
+
+    fn create_stream<'a>(
+        &'a self,
+        opts: &'a Opts,
+        encoder: &'a mut dyn SegmentEncoder,
+    ) -> Result<Self::Stream<'a>> {
+        Ok(OpenAiStream {
+            backend: self,
+            opts,
+            encoder,
+            buffer: Vec::new(),
+            samples_sent: 0,
+        })
+    }
+}
+
+/// Streaming state for [`OpenAiBackend`]: buffers audio and sends it a chunk at a time.
+pub struct OpenAiStream<'a> {
+    backend: &'a OpenAiBackend,
+    opts: &'a Opts,
+    encoder: &'a mut dyn SegmentEncoder,
+    buffer: Vec<f32>,
+    samples_sent: usize,
+}
+
+impl OpenAiStream<'_> {
+    fn send_buffer(&mut self) -> Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        let offset_seconds = self.samples_sent as f32 / TARGET_SAMPLE_RATE as f32;
+        self.backend
+            .emit(self.opts, self.encoder, &self.buffer, offset_seconds)?;
+        self.samples_sent += self.buffer.len();
+        self.buffer.clear();
+        Ok(())
+    }
+}
+
+impl BackendStream for OpenAiStream<'_> {
+    fn on_samples(&mut self, samples_16k_mono: &[f32]) -> Result<bool> {
+        let chunk_samples = self.backend.chunk_samples();
+        let mut rest = samples_16k_mono;
+        while !rest.is_empty() {
+            let take = (chunk_samples - self.buffer.len()).min(rest.len());
+            let (now, later) = rest.split_at(take);
+            self.buffer.extend_from_slice(now);
+            rest = later;
+            if self.buffer.len() == chunk_samples {
+                self.send_buffer()?;
+            }
+        }
+        Ok(true)
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        self.send_buffer()
+    }
+}
+
+fn encode_wav(samples: &[f32]) -> AnyResult<Vec<u8>> {
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: TARGET_SAMPLE_RATE,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+
+    let mut out = Cursor::new(Vec::new());
+    let mut writer = hound::WavWriter::new(&mut out, spec).context("failed to start WAV")?;
+    for &sample in samples {
+        writer.write_sample((sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16)?;
+    }
+    writer.finalize().context("failed to finish WAV")?;
+    Ok(out.into_inner())
+}
+
+/// The subset of the `verbose_json` response that Scribble uses.
+#[derive(Debug, Deserialize)]
+struct VerboseTranscription {
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    segments: Vec<VerboseSegment>,
+    #[serde(default)]
+    words: Vec<VerboseWord>,
+}
+
+#[derive(Debug, Deserialize)]
+struct VerboseSegment {
+    start: f32,
+    end: f32,
+    text: String,
+    #[serde(default)]
+    avg_logprob: f32,
+    #[serde(default)]
+    no_speech_prob: f32,
+}
+
+#[derive(Debug, Deserialize)]
+struct VerboseWord {
+    word: String,
+    start: f32,
+    end: f32,
+}
+
+impl VerboseTranscription {
+    fn into_segments(self, opts: &Opts, sample_count: usize) -> Vec<Segment> {
+        let language_code = opts.language.clone().unwrap_or_else(|| "und".to_owned());
+
+        // Services that only return `text` still get one segment spanning the whole buffer.
+        let segments = if self.segments.is_empty() && !self.text.trim().is_empty() {
+            vec![VerboseSegment {
+                start: 0.0,
+                end: sample_count as f32 / TARGET_SAMPLE_RATE as f32,
+                text: self.text,
+                avg_logprob: 0.0,
+                no_speech_prob: 0.0,
+            }]
+        } else {
+            self.segments
+        };
+
+        let mut words = self.words.into_iter().peekable();
+        let mut out = Vec::with_capacity(segments.len());
+        for (idx, segment) in segments.iter().enumerate() {
+            let is_last = idx + 1 == segments.len();
+            // The service reports words for the whole request; hand each segment the words that
+            // start before it ends (the last segment takes any stragglers).
+            let segment_words: Vec<VerboseWord> =
+                std::iter::from_fn(|| words.next_if(|word| is_last || word.start < segment.end))
+                    .collect();
+
+            let probability = segment.avg_logprob.exp().clamp(0.0, 1.0);
+            let tokens: Vec<Token> = segment_words
+                .iter()
+                .map(|word| Token {
+                    start_seconds: word.start,
+                    end_seconds: word.end,
+                    text: format!(" {}", word.word.trim()),
+                    probability,
+                    kind: TokenKind::Text,
+                })
+                .collect();
+            let words: Vec<Word> = segment_words
+                .iter()
+                .map(|word| Word {
+                    start_seconds: word.start,
+                    end_seconds: word.end,
+                    text: word.word.trim().to_owned(),
+                    probability,
+                })
+                .collect();
+
+            let has_text = !segment.text.trim().is_empty();
+            let base = Segment {
+                start_seconds: segment.start,
+                end_seconds: segment.end,
+                text: segment.text.clone(),
+                tokens,
+                words,
+                language_code: language_code.clone(),
+                next_speaker_turn: false,
+                avg_logprob: segment.avg_logprob,
+                no_speech_probability: segment.no_speech_prob,
+                confidence: Segment::confidence_from(
+                    segment.avg_logprob,
+                    segment.no_speech_prob,
+                    has_text,
+                ),
+                from_fallback: false,
+                channel: None,
+            };
+
+            if opts.enable_word_level_segments && !base.words.is_empty() {
+                out.extend(split_into_words(&base));
+            } else {
+                out.push(base);
+            }
+        }
+        out
+    }
+}
+
+/// One segment per word, keeping the parent segment's scores.
+fn split_into_words(segment: &Segment) -> Vec<Segment> {
+    segment
+        .tokens
+        .iter()
+        .zip(&segment.words)
+        .map(|(token, word)| Segment {
+            start_seconds: word.start_seconds,
+            end_seconds: word.end_seconds,
+            text: token.text.clone(),
+            tokens: vec![token.clone()],
+            words: vec![word.clone()],
+            ..segment.clone()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+
+    use super::*;
+
+    /// Serve `responses` (status line, body) to successive requests and report each request
+    /// as received.
+    fn mock_server(responses: Vec<(&'static str, String)>) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock server");
+        let url = format!("http://{}", listener.local_addr().expect("local addr"));
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().expect("accept");
+                let mut reader = BufReader::new(stream);
+
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).expect("read header");
+                    if let Some((name, value)) = line.split_once(':')
+                        && name.eq_ignore_ascii_case("content-length")
+                    {
+                        content_length = value.trim().parse().expect("content length");
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut payload = vec![0; content_length];
+                reader.read_exact(&mut payload).expect("read body");
+                request.push_str(&String::from_utf8_lossy(&payload));
+                tx.send(request).expect("report request");
+
+                let response = format!(
+                    "HTTP/1.1 {status}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                reader
+                    .get_mut()
+                    .write_all(response.as_bytes())
+                    .expect("write response");
+            }
+        });
+
+        (url, rx)
+    }
+
+    fn verbose_json(text: &str, end: f32) -> String {
+        serde_json::json!({
+            "text": text,
+            "segments": [
+                { "start": 0.0, "end": end, "text": text, "avg_logprob": -0.1, "no_speech_prob": 0.05 }
+            ],
+            "words": [
+                { "word": "ask", "start": 0.1, "end": 0.3 },
+                { "word": "not", "start": 0.3, "end": 0.5 }
+            ]
+        })
+        .to_string()
+    }
+
+    #[derive(Default)]
+    struct Collect(Vec<Segment>);
+
+    impl SegmentEncoder for Collect {
+        fn write_segment(&mut self, seg: &Segment) -> Result<()> {
+            self.0.push(seg.clone());
+            Ok(())
+        }
+
+        fn close(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn opts() -> Opts {
+        Opts {
+            language: Some("en".to_owned()),
+            ..Opts::default()
+        }
+    }
+
+    #[test]
+    fn full_buffer_posts_wav_and_maps_verbose_json() -> anyhow::Result<()> {
+        let (url, requests) = mock_server(vec![("200 OK", verbose_json(" ask not", 0.6))]);
+        let backend = OpenAiBackend::new(url)?
+            .with_api_key("secret")
+            .with_model("whisper-large");
+        let mut out = Collect::default();
+
+        let opts = Opts {
+            model_key: Some("ggml-base.en.bin".to_owned()),
+            ..opts()
+        };
+
+        backend.transcribe_full(&opts, &mut out, &[0.0; 16_000])?;
+
+        let request = requests.recv()?;
+        assert!(request.starts_with("POST /v1/audio/transcriptions "));
+        assert!(request.contains("authorization: Bearer secret"));
+        assert!(request.contains("verbose_json"));
+        assert!(request.contains("whisper-large"));
+        assert!(!request.contains("ggml-base.en.bin"));
+        assert!(request.contains("RIFF"));
+
+        let [segment] = out.0.as_slice() else {
+            panic!("expected one segment, got {:?}", out.0);
+        };
+        assert_eq!(segment.text, " ask not");
+        assert_eq!(segment.language_code, "en");
+        assert_eq!(segment.avg_logprob, -0.1);
+        assert_eq!(segment.no_speech_probability, 0.05);
+        assert!(segment.confidence > 0.8);
+        let words: Vec<_> = segment.words.iter().map(|w| w.text.as_str()).collect();
+        assert_eq!(words, ["ask", "not"]);
+        assert_eq!(segment.tokens[1].text, " not");
+        Ok(())
+    }
+
+    #[test]
+    fn stream_sends_fixed_chunks_and_offsets_timestamps() -> anyhow::Result<()> {
+        let (url, requests) = mock_server(vec![
+            ("200 OK", verbose_json(" first", 1.0)),
+            ("200 OK", verbose_json(" second", 0.5)),
+        ]);
+        let backend = OpenAiBackend::new(url)?.with_chunk_seconds(1);
+        let opts = opts();
+        let mut out = Collect::default();
+
+        let mut stream = backend.create_stream(&opts, &mut out)?;
+        assert!(stream.on_samples(&[0.0; 10_000])?);
+        assert!(stream.on_samples(&[0.0; 14_000])?);
+        stream.finish()?;
+
+        assert_eq!(requests.iter().take(2).count(), 2);
+        let starts: Vec<_> = out.0.iter().map(|s| s.start_seconds).collect();
+        assert_eq!(starts, [0.0, 1.0]);
+        assert_eq!(out.0[1].words[0].start_seconds, 1.1);
+        Ok(())
+    }
+
+    #[test]
+    fn full_buffer_sends_one_request_per_chunk() -> anyhow::Result<()> {
+        let (url, requests) = mock_server(vec![
+            ("200 OK", verbose_json(" first", 1.0)),
+            ("200 OK", verbose_json(" second", 1.0)),
+            ("200 OK", verbose_json(" third", 0.5)),
+        ]);
+        let backend = OpenAiBackend::new(url)?.with_chunk_seconds(1);
+        let mut out = Collect::default();
+
+        backend.transcribe_full(&opts(), &mut out, &[0.0; 40_000])?;
+
+        assert_eq!(requests.iter().take(3).count(), 3);
+        let starts: Vec<_> = out.0.iter().map(|s| s.start_seconds).collect();
+        assert_eq!(starts, [0.0, 1.0, 2.0]);
+        assert_eq!(out.0[2].words[0].start_seconds, 2.1);
+        Ok(())
+    }
+
+    #[test]
+    fn http_errors_surface_as_backend_errors() -> anyhow::Result<()> {
+        let (url, _requests) = mock_server(vec![(
+            "429 Too Many Requests",
+            r#"{"error":"slow down"}"#.to_owned(),
+        )]);
+        let backend = OpenAiBackend::new(url)?;
+
+        let err = backend
+            .transcribe_full(&opts(), &mut Collect::default(), &[0.0; 1_600])
+            .unwrap_err();
+
+        assert!(matches!(err, crate::Error::Backend { .. }), "{err:?}");
+        let source = std::error::Error::source(&err).map(ToString::to_string);
+        assert!(source.unwrap_or_default().contains("429"));
+        Ok(())
+    }
+}
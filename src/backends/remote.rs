@@ -0,0 +1,589 @@
+//! Backend that offloads inference to a remote `scribble-server`.
+
+use std::sync::mpsc;
+use std::time::Duration;
+
+use anyhow::{Context, Result as AnyResult, bail};
+use futures_util::StreamExt;
+use reqwest::Client;
+use reqwest::header::CONTENT_TYPE;
+use serde::Deserialize;
+use tokio::runtime::{Handle, Runtime};
+
+use crate::Result;
+use crate::audio_pipeline::TARGET_SAMPLE_RATE;
+use crate::backend::{Backend, BackendStream};
+use crate::commit_policy::CommitPolicy;
+use crate::opts::Opts;
+use crate::segment_encoder::SegmentEncoder;
+use crate::segments::Segment;
+
+/// Audio chunks queued for upload before `on_samples` blocks; bounds memory when the network
+/// is slower than decoding.
+const UPLOAD_QUEUE_CHUNKS: usize = 64;
+
+/// How often `finish` checks for cancellation while waiting on the server.
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Backend that streams normalized audio to a remote `scribble-server` and reads its segments
+/// back.
+///
+/// Scribble still decodes, resamples and applies VAD locally; only inference runs remotely. Each
+/// transcription is a single `POST /transcribe` whose body is a streamed 16-bit mono WAV at
+/// Scribble's target sample rate, so segments arrive while audio is still being uploaded. The
+/// server is asked for JSON Lines output, and `Opts` settings that affect inference (model key,
+/// language, translation, commit policy, partial results, word-level segments) are forwarded as
+/// query parameters. Hallucination filtering runs locally.
+///
+/// Both full-buffer and streaming modes use the server's streaming transcriber.
+///
+/// The backend owns a small Tokio runtime for its requests. It can be created and dropped
+/// anywhere, including inside async code; transcription itself blocks, so call it from a plain
+/// or blocking-pool thread.
+pub struct RemoteBackend {
+    client: Client,
+    base_url: String,
+    handle: Handle,
+    /// Kept only to own the runtime; taken and shut down without blocking on drop.
+    runtime: Option<Runtime>,
+}
+
+impl RemoteBackend {
+    /// Create a backend for the `scribble-server` at `base_url` (e.g. `http://10.0.0.5:8080`).
+    pub fn new(base_url: impl Into<String>) -> Result<Self> {
+        let client = Client::builder()
+            .user_agent(concat!("scribble/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .context("failed to build HTTP client")?;
+
+        // Requests run on a private runtime so the backend works from plain threads; one worker
+        // is enough since it only shuttles bytes.
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("scribble-remote")
+            .enable_all()
+            .build()
+            .context("failed to start HTTP runtime")?;
+
+        Ok(Self {
+            client,
+            base_url: base_url.into().trim_end_matches('/').to_owned(),
+            handle: runtime.handle().clone(),
+            runtime: Some(runtime),
+        })
+    }
+
+    /// Use a preconfigured HTTP client (for custom timeouts, proxies or TLS roots).
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+}
+
+impl Drop for RemoteBackend {
+    fn drop(&mut self) {
+        // Dropping a `Runtime` waits for its worker and panics inside async code. Streams borrow
+        // the backend, so no request is still wanted by the time it is dropped.
+        if let Some(runtime) = self.runtime.take() {
+            runtime.shutdown_background();
+        }
+    }
+}
+
+impl Backend for RemoteBackend {
+    type Stream<'a> = RemoteStream<'a>;
+
+    fn transcribe_full(
+        &self,
+        opts: &Opts,
+        encoder: &mut dyn SegmentEncoder,
+        samples: &[f32],
+    ) -> Result<()> {
+        let mut stream = self.create_stream(opts, encoder)?;
+        stream.on_samples(samples)?;
+        stream.finish()
+    }
+
+    fn create_stream<'a>(
+        &'a self,
+        opts: &'a Opts,
+        encoder: &'a mut dyn SegmentEncoder,
+    ) -> Result<Self::Stream<'a>> {
+        let (upload_tx, upload_rx) = tokio::sync::mpsc::channel(UPLOAD_QUEUE_CHUNKS);
+        let (events_tx, events_rx) = mpsc::channel();
+
+        let request = self
+            .client
+            .post(format!("{}/transcribe", self.base_url))
+            .query(&query_params(opts))
+            .header(CONTENT_TYPE, "audio/wav");
+        let task = self
+            .handle
+            .spawn(run_request(request, upload_rx, events_tx));
+
+        Ok(RemoteStream {
+            opts,
+            encoder,
+            upload_tx: Some(upload_tx),
+            events_rx,
+            task,
+        })
+    }
+}
+
+/// Streaming state for [`RemoteBackend`]: one in-flight request per stream.
+///
+/// Dropping the stream aborts its request.
+pub struct RemoteStream<'a> {
+    opts: &'a Opts,
+    encoder: &'a mut dyn SegmentEncoder,
+    upload_tx: Option<tokio::sync::mpsc::Sender<Vec<u8>>>,
+    events_rx: mpsc::Receiver<Event>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl Drop for RemoteStream<'_> {
+    fn drop(&mut self) {
+        // A no-op once the response has been read; otherwise stops waiting on the server.
+        self.task.abort();
+    }
+}
+
+/// What the request task reports back to the stream.
+enum Event {
+    Final(Segment),
+    Partial(Segment),
+    Failed(anyhow::Error),
+}
+
+impl RemoteStream<'_> {
+    fn handle(&mut self, event: Event) -> Result<()> {
+        match event {
+            Event::Final(segment) => self.encoder.write_segment(&segment),
+            Event::Partial(segment) => self.encoder.write_partial(&[segment]),
+            Event::Failed(err) => Err(crate::Error::Backend {
+                message: "remote transcription failed".to_owned(),
+                source: Some(err.into()),
+            }),
+        }
+    }
+
+    /// Forward every event that has already arrived, without waiting.
+    fn drain_ready(&mut self) -> Result<()> {
+        while let Ok(event) = self.events_rx.try_recv() {
+            self.handle(event)?;
+        }
+        Ok(())
+    }
+}
+
+impl BackendStream for RemoteStream<'_> {
+    fn on_samples(&mut self, samples_16k_mono: &[f32]) -> Result<bool> {
+        self.drain_ready()?;
+
+        let Some(upload_tx) = &self.upload_tx else {
+            return Ok(false);
+        };
+        if upload_tx
+            .blocking_send(pcm_s16le(samples_16k_mono))
+            .is_err()
+        {
+            // The request ended early; `finish` reports why.
+            self.upload_tx = None;
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        // Closing the upload ends the request body; the server then flushes its last segments.
+        self.upload_tx = None;
+        loop {
+            if self.opts.is_cancelled() {
+                return Err(crate::Error::Cancelled);
+            }
+            match self.events_rx.recv_timeout(CANCEL_POLL_INTERVAL) {
+                Ok(event) => self.handle(event)?,
+                Err(mpsc::RecvTimeoutError::Timeout) => continue,
+                Err(mpsc::RecvTimeoutError::Disconnected) => return Ok(()),
+            }
+        }
+    }
+}
+
+async fn run_request(
+    request: reqwest::RequestBuilder,
+    upload_rx: tokio::sync::mpsc::Receiver<Vec<u8>>,
+    events_tx: mpsc::Sender<Event>,
+) {
+    if let Err(err) = stream_request(request, upload_rx, &events_tx).await {
+        let _ = events_tx.send(Event::Failed(err));
+    }
+}
+
+async fn stream_request(
+    request: reqwest::RequestBuilder,
+    upload_rx: tokio::sync::mpsc::Receiver<Vec<u8>>,
+    events_tx: &mpsc::Sender<Event>,
+) -> AnyResult<()> {
+    let samples = futures_util::stream::unfold(upload_rx, |mut rx| async move {
+        let chunk = rx.recv().await?;
+        Some((Ok::<_, std::io::Error>(chunk), rx))
+    });
+    let body = futures_util::stream::once(async { Ok(streaming_wav_header()) }).chain(samples);
+
+    let mut response = request
+        .body(reqwest::Body::wrap_stream(body))
+        .send()
+        .await
+        .context("request to scribble-server failed")?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        let message = serde_json::from_str::<ErrorBody>(&body)
+            .map(|err| err.error)
+            .unwrap_or(body);
+        bail!("scribble-server returned {status}: {}", message.trim());
+    }
+
+    let mut pending = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .context("failed to read scribble-server response")?
+    {
+        pending.extend_from_slice(&chunk);
+        while let Some(newline) = pending.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = pending.drain(..=newline).collect();
+            if let Some(event) = parse_line(&line)? {
+                // The stream may have been dropped after an encoder error; nobody is listening.
+                if events_tx.send(event).is_err() {
+                    return Ok(());
+                }
+            }
+        }
+    }
+    if let Some(event) = parse_line(&pending)? {
+        let _ = events_tx.send(event);
+    }
+    Ok(())
+}
+
+/// One JSON Lines record from `scribble-server`.
+#[derive(Deserialize)]
+struct Line {
+    status: LineStatus,
+    #[serde(flatten)]
+    segment: Segment,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum LineStatus {
+    Final,
+    Partial,
+}
+
+#[derive(Deserialize)]
+struct ErrorBody {
+    error: String,
+}
+
+fn parse_line(line: &[u8]) -> AnyResult<Option<Event>> {
+    if line.trim_ascii().is_empty() {
+        return Ok(None);
+    }
+    let line: Line =
+        serde_json::from_slice(line).context("malformed segment from scribble-server")?;
+    Ok(Some(match line.status {
+        LineStatus::Final => Event::Final(line.segment),
+        LineStatus::Partial => Event::Partial(line.segment),
+    }))
+}
+
+/// Server query parameters for the inference-related parts of `opts`.
+fn query_params(opts: &Opts) -> Vec<(&'static str, String)> {
+    let mut params = vec![
+        ("output", "jsonl".to_owned()),
+        // VAD already ran locally; the server only sees speech.
+        ("enable_vad", "false".to_owned()),
+        (
+            "translate_to_english",
+            opts.enable_translate_to_english.to_string(),
+        ),
+        (
+            "max_buffer_seconds",
+            opts.incremental_max_buffer_seconds.to_string(),
+        ),
+        ("partial_results", opts.enable_partial_results.to_string()),
+        (
+            "word_level_segments",
+            opts.enable_word_level_segments.to_string(),
+        ),
+        ("non_text_tokens", opts.enable_non_text_tokens.to_string()),
+    ];
+    if let Some(model_key) = &opts.model_key {
+        params.push(("model_key", model_key.clone()));
+    }
+    if let Some(language) = &opts.language {
+        params.push(("language", language.clone()));
+    }
+    if let CommitPolicy::LocalAgreement { n } = opts.incremental_commit_policy {
+        params.push(("local_agreement", n.to_string()));
+    }
+    params
+}
+
+/// WAV header for a stream of unknown length.
+///
+/// RIFF and data sizes are set to `u32::MAX`, the convention ffmpeg uses when writing WAV to a
+/// pipe; readers then treat the data chunk as running until end of input.
+fn streaming_wav_header() -> Vec<u8> {
+    const CHANNELS: u16 = 1;
+    const BITS_PER_SAMPLE: u16 = 16;
+    let block_align = CHANNELS * BITS_PER_SAMPLE / 8;
+    let byte_rate = TARGET_SAMPLE_RATE * u32::from(block_align);
+
+    let mut header = Vec::with_capacity(44);
+    header.extend_from_slice(b"RIFF");
+    header.extend_from_slice(&u32::MAX.to_le_bytes());
+    header.extend_from_slice(b"WAVEfmt ");
+    header.extend_from_slice(&16u32.to_le_bytes());
+    header.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    header.extend_from_slice(&CHANNELS.to_le_bytes());
+    header.extend_from_slice(&TARGET_SAMPLE_RATE.to_le_bytes());
+    header.extend_from_slice(&byte_rate.to_le_bytes());
+    header.extend_from_slice(&block_align.to_le_bytes());
+    header.extend_from_slice(&BITS_PER_SAMPLE.to_le_bytes());
+    header.extend_from_slice(b"data");
+    header.extend_from_slice(&u32::MAX.to_le_bytes());
+    header
+}
+
+fn pcm_s16le(samples: &[f32]) -> Vec<u8> {
+    samples
+        .iter()
+        .flat_map(|&sample| ((sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16).to_le_bytes())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    use super::*;
+
+    /// Accept one request, wait for its whole chunked body, then answer with `status` and
+    /// `body`. Returns the base URL and a handle yielding the raw request.
+    fn mock_server(status: &'static str, body: String) -> (String, thread::JoinHandle<Vec<u8>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock server");
+        let url = format!("http://{}", listener.local_addr().expect("local addr"));
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().expect("accept");
+            let mut reader = BufReader::new(stream);
+
+            // The upload is chunked; its terminating zero-length chunk ends the request.
+            let mut request = Vec::new();
+            while !request.ends_with(b"\r\n0\r\n\r\n") {
+                let mut byte = [0u8; 1];
+                reader.read_exact(&mut byte).expect("read request");
+                request.push(byte[0]);
+            }
+
+            let response = format!(
+                "HTTP/1.1 {status}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            reader
+                .get_mut()
+                .write_all(response.as_bytes())
+                .expect("write response");
+            request
+        });
+
+        (url, handle)
+    }
+
+    fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+        haystack
+            .windows(needle.len())
+            .any(|window| window == needle)
+    }
+
+    fn line(status: &str, text: &str, start: f32) -> String {
+        serde_json::json!({
+            "status": status,
+            "start_seconds": start,
+            "end_seconds": start + 1.0,
+            "text": text,
+            "tokens": [],
+            "words": [],
+            "language_code": "en",
+            "next_speaker_turn": false,
+            "avg_logprob": -0.2,
+            "no_speech_probability": 0.1,
+            "confidence": 0.7,
+            "from_fallback": false
+        })
+        .to_string()
+    }
+
+    #[derive(Default)]
+    struct Collect {
+        finals: Vec<String>,
+        partials: Vec<String>,
+    }
+
+    impl SegmentEncoder for Collect {
+        fn write_segment(&mut self, seg: &Segment) -> Result<()> {
+            self.finals.push(seg.text.clone());
+            Ok(())
+        }
+
+        fn write_partial(&mut self, segments: &[Segment]) -> Result<()> {
+            self.partials
+                .extend(segments.iter().map(|seg| seg.text.clone()));
+            Ok(())
+        }
+
+        fn close(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn opts() -> Opts {
+        Opts {
+            model_key: Some("ggml-tiny.bin".to_owned()),
+            enable_voice_activity_detection: true,
+            output_type: crate::OutputType::Vtt,
+            incremental_commit_policy: CommitPolicy::LocalAgreement { n: 2 },
+            enable_partial_results: true,
+            ..Opts::default()
+        }
+    }
+
+    #[test]
+    fn streams_wav_and_forwards_json_lines() -> anyhow::Result<()> {
+        let body = [
+            line("partial", " and so", 0.0),
+            line("final", " and so my fellow", 0.0),
+            line("final", " Americans", 1.0),
+        ]
+        .join("\n");
+        let (url, server) = mock_server("200 OK", body + "\n");
+        let backend = RemoteBackend::new(url)?;
+        let opts = opts();
+        let mut out = Collect::default();
+
+        let mut stream = backend.create_stream(&opts, &mut out)?;
+        assert!(stream.on_samples(&[0.25; 1_600])?);
+        assert!(stream.on_samples(&[-0.25; 1_600])?);
+        stream.finish()?;
+        drop(stream);
+
+        assert_eq!(out.finals, [" and so my fellow", " Americans"]);
+        assert_eq!(out.partials, [" and so"]);
+
+        let request = server.join().expect("mock server");
+        let head = String::from_utf8_lossy(&request);
+        assert!(head.starts_with("POST /transcribe?output=jsonl&enable_vad=false"));
+        assert!(head.contains("model_key=ggml-tiny.bin"));
+        assert!(head.contains("local_agreement=2"));
+        assert!(contains(&request, b"RIFF"));
+        Ok(())
+    }
+
+    #[test]
+    fn server_errors_surface_as_backend_errors() -> anyhow::Result<()> {
+        let (url, _server) = mock_server(
+            "415 Unsupported Media Type",
+            r#"{"error":"unsupported or unrecognized media container"}"#.to_owned(),
+        );
+        let backend = RemoteBackend::new(url)?;
+
+        let err = backend
+            .transcribe_full(&opts(), &mut Collect::default(), &[0.0; 1_600])
+            .unwrap_err();
+
+        assert!(matches!(err, crate::Error::Backend { .. }), "{err:?}");
+        let source = std::error::Error::source(&err).map(ToString::to_string);
+        assert!(
+            source
+                .unwrap_or_default()
+                .contains("unrecognized media container")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn finish_returns_when_cancelled_while_the_server_hangs() -> anyhow::Result<()> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let url = format!("http://{}", listener.local_addr()?);
+        // Read the request forever without ever answering.
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().expect("accept");
+            let _ = std::io::copy(&mut stream, &mut std::io::sink());
+        });
+
+        let backend = RemoteBackend::new(url)?;
+        let token = crate::CancellationToken::new();
+        let opts = Opts {
+            cancellation_token: Some(token.clone()),
+            ..opts()
+        };
+        let mut out = Collect::default();
+        let mut stream = backend.create_stream(&opts, &mut out)?;
+        stream.on_samples(&[0.0; 1_600])?;
+
+        let canceller = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(200));
+            token.cancel();
+        });
+        let err = stream.finish().unwrap_err();
+        canceller.join().expect("canceller");
+
+        assert!(matches!(err, crate::Error::Cancelled), "{err:?}");
+        Ok(())
+    }
+
+    #[derive(Default)]
+    struct Samples(Vec<f32>);
+
+    impl crate::decoder::SamplesSink for Samples {
+        fn on_samples(&mut self, samples_16k_mono: &[f32]) -> anyhow::Result<bool> {
+            self.0.extend_from_slice(samples_16k_mono);
+            Ok(true)
+        }
+    }
+
+    #[test]
+    fn can_be_dropped_inside_async_code() -> anyhow::Result<()> {
+        let runtime = tokio::runtime::Builder::new_current_thread().build()?;
+        runtime.block_on(async {
+            let backend = RemoteBackend::new("http://127.0.0.1:9")?;
+            drop(backend);
+            anyhow::Ok(())
+        })
+    }
+
+    #[test]
+    fn streaming_wav_is_understood_by_the_decoder() -> anyhow::Result<()> {
+        let mut wav = streaming_wav_header();
+        wav.extend(pcm_s16le(&[0.5; 32_000]));
+
+        let mut samples = Samples::default();
+        crate::decoder::decode_to_stream_from_read(
+            std::io::Cursor::new(wav),
+            Default::default(),
+            &mut samples,
+        )?;
+
+        assert_eq!(samples.0.len(), 32_000);
+        assert!(samples.0.iter().all(|&s| (s - 0.5).abs() < 1e-3));
+        Ok(())
+    }
+}
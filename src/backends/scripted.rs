@@ -0,0 +1,240 @@
+//! A deterministic backend for tests that must not depend on a real model.
+
+use crate::Result;
+use crate::backend::{Backend, BackendStream};
+use crate::opts::Opts;
+use crate::segment_encoder::SegmentEncoder;
+use crate::segments::Segment;
+
+/// What a [`ScriptedBackend`] does once it reaches a step's sample offset.
+#[derive(Debug, Clone)]
+enum Action {
+    Emit(Segment),
+    Fail(String),
+    Stop,
+}
+
+#[derive(Debug, Clone)]
+struct Step {
+    at_sample: usize,
+    action: Action,
+}
+
+/// A [`Backend`] that replays a pre-programmed script instead of running inference.
+///
+/// Each step fires once the backend has been fed at least `at_sample` samples (mono, at
+/// Scribble's target sample rate), in the order the steps were added. Steps can emit a segment,
+/// fail with [`crate::Error::Backend`], or stop the stream early. Steps whose offset is never
+/// reached do not fire.
+///
+/// Offsets are relative to the buffer the backend is given: the whole input when streaming or
+/// in full-buffer mode, or the current chunk in parallel mode. Segment timestamps are emitted as
+/// scripted.
+///
+/// ```
+/// use scribble::{Scribble, ScriptedBackend};
+///
+/// let backend = ScriptedBackend::new()
+///     .text_at(16_000, 0.0, 1.0, " hello")
+///     .error_at(32_000, "model exploded");
+/// let scribble = Scribble::with_backend(backend);
+/// # let _ = scribble;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ScriptedBackend {
+    steps: Vec<Step>,
+}
+
+impl ScriptedBackend {
+    /// Create a backend with an empty script; it accepts any input and emits nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Emit `segment` once `at_sample` samples have been seen.
+    pub fn segment_at(self, at_sample: usize, segment: Segment) -> Self {
+        self.push(at_sample, Action::Emit(segment))
+    }
+
+    /// Emit a plain text segment spanning `start_seconds..end_seconds` once `at_sample` samples
+    /// have been seen.
+    pub fn text_at(
+        self,
+        at_sample: usize,
+        start_seconds: f32,
+        end_seconds: f32,
+        text: impl Into<String>,
+    ) -> Self {
+        let segment = Segment {
+            start_seconds,
+            end_seconds,
+            text: text.into(),
+            language_code: "und".to_owned(),
+            confidence: 1.0,
+            ..Segment::default()
+        };
+        self.segment_at(at_sample, segment)
+    }
+
+    /// Fail with [`crate::Error::Backend`] carrying `message` once `at_sample` samples have been
+    /// seen.
+    pub fn error_at(self, at_sample: usize, message: impl Into<String>) -> Self {
+        self.push(at_sample, Action::Fail(message.into()))
+    }
+
+    /// Ask the caller to stop feeding samples once `at_sample` samples have been seen.
+    pub fn stop_at(self, at_sample: usize) -> Self {
+        self.push(at_sample, Action::Stop)
+    }
+
+    fn push(mut self, at_sample: usize, action: Action) -> Self {
+        self.steps.push(Step { at_sample, action });
+        self
+    }
+}
+
+impl Backend for ScriptedBackend {
+    type Stream<'a> = ScriptedStream<'a>;
+
+    fn transcribe_full(
+        &self,
+        _opts: &Opts,
+        encoder: &mut dyn SegmentEncoder,
+        samples: &[f32],
+    ) -> Result<()> {
+        let mut stream = ScriptedStream {
+            steps: &self.steps,
+            next_step: 0,
+            samples_seen: samples.len(),
+            encoder,
+        };
+        stream.run_due_steps()?;
+        Ok(())
+    }
+
+    fn create_stream<'a>(
+        &'a self,
+        _opts: &'a Opts,
+        encoder: &'a mut dyn SegmentEncoder,
+    ) -> Result<Self::Stream<'a>> {
+        Ok(ScriptedStream {
+            steps: &self.steps,
+            next_step: 0,
+            samples_seen: 0,
+            encoder,
+        })
+    }
+}
+
+/// Streaming state returned by [`ScriptedBackend::create_stream`].
+pub struct ScriptedStream<'a> {
+    steps: &'a [Step],
+    next_step: usize,
+    samples_seen: usize,
+    encoder: &'a mut dyn SegmentEncoder,
+}
+
+impl ScriptedStream<'_> {
+    /// Fire every not-yet-run step in script order until one lies beyond `samples_seen`.
+    ///
+    /// Returns `Ok(false)` when a stop step fired.
+    fn run_due_steps(&mut self) -> Result<bool> {
+        while let Some(step) = self.steps.get(self.next_step) {
+            if step.at_sample > self.samples_seen {
+                break;
+            }
+            self.next_step += 1;
+
+            match &step.action {
+                Action::Emit(segment) => self.encoder.write_segment(segment)?,
+                Action::Fail(message) => {
+                    return Err(crate::Error::Backend {
+                        message: message.clone(),
+                        source: None,
+                    });
+                }
+                Action::Stop => return Ok(false),
+            }
+        }
+        Ok(true)
+    }
+}
+
+impl BackendStream for ScriptedStream<'_> {
+    fn on_samples(&mut self, samples_16k_mono: &[f32]) -> Result<bool> {
+        self.samples_seen += samples_16k_mono.len();
+        self.run_due_steps()
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        self.run_due_steps()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Texts(Vec<String>);
+
+    impl SegmentEncoder for Texts {
+        fn write_segment(&mut self, seg: &Segment) -> Result<()> {
+            self.0.push(seg.text.clone());
+            Ok(())
+        }
+
+        fn close(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn stream_fires_steps_as_offsets_are_reached() -> anyhow::Result<()> {
+        let backend = ScriptedBackend::new()
+            .text_at(0, 0.0, 0.5, "a")
+            .text_at(10, 0.5, 1.0, "b")
+            .text_at(100, 1.0, 2.0, "never");
+        let opts = Opts::default();
+        let mut texts = Texts::default();
+
+        let mut stream = backend.create_stream(&opts, &mut texts)?;
+        assert!(stream.on_samples(&[0.0; 5])?);
+        assert!(stream.on_samples(&[0.0; 5])?);
+        stream.finish()?;
+
+        assert_eq!(texts.0, ["a", "b"]);
+        Ok(())
+    }
+
+    #[test]
+    fn stream_reports_errors_and_stops() -> anyhow::Result<()> {
+        let opts = Opts::default();
+        let mut texts = Texts::default();
+
+        let stopping = ScriptedBackend::new().stop_at(4);
+        let mut stream = stopping.create_stream(&opts, &mut texts)?;
+        assert!(!stream.on_samples(&[0.0; 4])?);
+
+        let failing = ScriptedBackend::new().error_at(4, "boom");
+        let mut stream = failing.create_stream(&opts, &mut texts)?;
+        let err = stream.on_samples(&[0.0; 4]).unwrap_err();
+        assert!(matches!(err, crate::Error::Backend { .. }));
+        assert!(err.to_string().contains("boom"));
+        Ok(())
+    }
+
+    #[test]
+    fn full_buffer_fires_steps_within_the_buffer() -> anyhow::Result<()> {
+        let backend = ScriptedBackend::new()
+            .text_at(8, 0.0, 0.5, "in")
+            .text_at(9, 0.5, 1.0, "out");
+        let mut texts = Texts::default();
+
+        backend.transcribe_full(&Opts::default(), &mut texts, &[0.0; 8])?;
+
+        assert_eq!(texts.0, ["in"]);
+        Ok(())
+    }
+}
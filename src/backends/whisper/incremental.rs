@@ -3,31 +3,46 @@
 //! This module provides a small adapter that:
 //! - buffers decoded mono samples at Scribble's target sample rate
 //! - runs Whisper once the buffer reaches a minimum duration
-//! - emits the first completed segment
-//! - advances the buffer by that segment’s end timestamp
+//! - emits the segments (or leading words) the configured [`CommitPolicy`] considers final
+//! - advances the buffer by the last emitted segment’s end timestamp
+
+use std::collections::VecDeque;
 
 use anyhow::{Context, Result, ensure};
-use whisper_rs::WhisperContext;
 
 use crate::audio_pipeline::TARGET_SAMPLE_RATE;
+use crate::commit_policy::CommitPolicy;
 use crate::decoder::SamplesSink;
 use crate::opts::Opts;
 use crate::segment_encoder::SegmentEncoder;
-
-use super::segments::{run_whisper_full, to_segment};
-
-/// Maximum buffer size before forcing progress.
-///
-/// If Whisper keeps returning <= 1 segment, keeps accumulating audio until this cap and then emits
-/// whatever is available to avoid unbounded memory growth.
-const DEFAULT_MAX_BUFFER_SECONDS: usize = 30;
+use crate::segments::Segment;
+use crate::token::Word;
 
 /// Max exponential backoff when Whisper makes no progress (in multiples of `min_window_samples`).
 const MAX_BACKOFF_SHIFT: u32 = 4; // up to 16x
 
+/// A segment produced by one inference pass, timed relative to the start of the window.
+pub(crate) struct WindowSegment {
+    pub(crate) segment: Segment,
+
+    /// Whisper's segment-level end timestamp (centiseconds).
+    ///
+    /// Used to advance the window rather than the token-derived `segment.end_seconds`, which
+    /// can stop short of trailing audio that belongs to the emitted segment.
+    pub(crate) end_timestamp_cs: i64,
+}
+
+/// One inference pass over a window of mono samples at Scribble's target sample rate.
+///
+/// Implemented by `WhisperContext`; tests use a scripted implementation so commit policies can
+/// be exercised without loading a model.
+pub(crate) trait WindowInference {
+    fn infer(&self, opts: &Opts, samples: &[f32]) -> Result<Vec<WindowSegment>>;
+}
+
 /// A streaming `SamplesSink` that incrementally emits Whisper segments as audio arrives.
 pub(crate) struct BufferedSegmentTranscriber<'a> {
-    ctx: &'a WhisperContext,
+    ctx: &'a dyn WindowInference,
     opts: &'a Opts,
     encoder: &'a mut dyn SegmentEncoder,
 
@@ -36,6 +51,10 @@ pub(crate) struct BufferedSegmentTranscriber<'a> {
     next_infer_at_samples: usize,
     no_progress_runs: u32,
 
+    // Words of uncommitted hypotheses from previous passes (most recent last), aligned to the
+    // current window head. Only populated for `CommitPolicy::LocalAgreement`.
+    history: VecDeque<Vec<String>>,
+
     // Backing buffer for decoded samples. Uses an index (`head`) instead of draining on every
     // segment so advancing is cheap; occasionally compacts to keep memory usage reasonable.
     samples: Vec<f32>,
@@ -47,13 +66,14 @@ pub(crate) struct BufferedSegmentTranscriber<'a> {
 
 impl<'a> BufferedSegmentTranscriber<'a> {
     pub(crate) fn new(
-        ctx: &'a WhisperContext,
+        ctx: &'a dyn WindowInference,
         opts: &'a Opts,
         encoder: &'a mut dyn SegmentEncoder,
     ) -> Self {
         let min_window_seconds = opts.incremental_min_window_seconds.max(1);
-        let min_window_samples = TARGET_SAMPLE_RATE as usize * min_window_seconds;
-        let max_window_samples = TARGET_SAMPLE_RATE as usize * DEFAULT_MAX_BUFFER_SECONDS;
+        let max_buffer_seconds = opts.incremental_max_buffer_seconds.max(min_window_seconds);
+        let min_window_samples = (TARGET_SAMPLE_RATE as usize).saturating_mul(min_window_seconds);
+        let max_window_samples = (TARGET_SAMPLE_RATE as usize).saturating_mul(max_buffer_seconds);
         Self {
             ctx,
             opts,
@@ -62,6 +82,7 @@ impl<'a> BufferedSegmentTranscriber<'a> {
             max_window_samples,
             next_infer_at_samples: min_window_samples,
             no_progress_runs: 0,
+            history: VecDeque::new(),
             samples: Vec::new(),
             head: 0,
             advanced_samples: 0,
@@ -72,6 +93,7 @@ impl<'a> BufferedSegmentTranscriber<'a> {
         let _ = self.process_available(true)?;
         self.samples.clear();
         self.head = 0;
+        self.history.clear();
         Ok(())
     }
 
@@ -116,66 +138,47 @@ impl<'a> BufferedSegmentTranscriber<'a> {
             return Ok(Progress::NoOp);
         }
 
-        let state = run_whisper_full(self.ctx, self.opts, self.window())?;
-        let n_segments_i32 = state.full_n_segments();
-        if n_segments_i32 <= 0 {
+        if self.opts.is_cancelled() {
+            return Err(crate::Error::Cancelled.into());
+        }
+
+        let hypothesis = self.ctx.infer(self.opts, self.window())?;
+        if hypothesis.is_empty() {
             if !force_flush {
-                self.no_progress_runs = self.no_progress_runs.saturating_add(1);
-                self.next_infer_at_samples = next_infer_threshold(
-                    win_len,
-                    self.min_window_samples,
-                    self.max_window_samples,
-                    self.no_progress_runs,
-                );
+                self.record_no_progress(win_len);
             }
             return Ok(Progress::NoOp);
         }
-        let n_segments: usize = n_segments_i32
-            .try_into()
-            .context("whisper returned a negative segment count")?;
-
-        // Finalization rule:
-        // - If Whisper produced >= 2 segments, treat all but the last as “final”.
-        // - At end-of-stream (or max-buffer cap), flush everything available.
-        let emit_count = if force_flush {
-            n_segments
-        } else if n_segments >= 2 {
-            n_segments - 1
+
+        // At end-of-stream (or max-buffer cap), flush everything available. Otherwise the
+        // commit policy decides how much of the hypothesis is final.
+        let (committed, pending) = if force_flush {
+            (hypothesis, Vec::new())
         } else {
-            0
+            self.split_committed(hypothesis)
         };
 
-        if emit_count == 0 {
-            self.no_progress_runs = self.no_progress_runs.saturating_add(1);
-            self.next_infer_at_samples = next_infer_threshold(
-                win_len,
-                self.min_window_samples,
-                self.max_window_samples,
-                self.no_progress_runs,
-            );
-            return Ok(Progress::NoOp);
-        }
-
         let offset_seconds = self.advanced_samples as f32 / TARGET_SAMPLE_RATE as f32;
 
-        for segment_idx in 0..emit_count {
-            let whisper_segment = state
-                .get_segment(segment_idx as i32)
-                .with_context(|| format!("whisper segment {segment_idx} was missing"))?;
+        let Some(last_emitted) = committed.last() else {
+            self.write_partial(&pending, offset_seconds)?;
+            self.record_no_progress(win_len);
+            return Ok(Progress::NoOp);
+        };
 
-            let mut segment = to_segment(whisper_segment)?;
-            apply_time_offset(&mut segment, offset_seconds);
+        for window_segment in &committed {
+            let mut segment = window_segment.segment.clone();
+            segment.shift_by(offset_seconds);
             self.encoder
                 .write_segment(&segment)
                 .map_err(anyhow::Error::new)?;
         }
 
+        // Finals first, then whatever is still provisional in this pass.
+        self.write_partial(&pending, offset_seconds)?;
+
         // Advance by the end timestamp of the last emitted segment.
-        let last_emitted_idx = emit_count - 1;
-        let last_emitted = state
-            .get_segment(last_emitted_idx as i32)
-            .with_context(|| format!("whisper segment {last_emitted_idx} was missing"))?;
-        let end_samples = segment_end_samples(last_emitted.end_timestamp(), win_len)?;
+        let end_samples = segment_end_samples(last_emitted.end_timestamp_cs, win_len)?;
 
         if end_of_stream {
             // End-of-stream flush is a single inference pass: emit what Whisper produced and
@@ -189,6 +192,20 @@ impl<'a> BufferedSegmentTranscriber<'a> {
         }
         self.no_progress_runs = 0;
 
+        // Committed words are no longer part of any pending hypothesis; keep the remaining
+        // entries aligned with the new window head. A forced flush consumed everything.
+        if force_flush {
+            self.history.clear();
+        } else {
+            let committed_words: usize = committed
+                .iter()
+                .map(|window_segment| segment_words(&window_segment.segment).len())
+                .sum();
+            for previous in &mut self.history {
+                previous.drain(..committed_words.min(previous.len()));
+            }
+        }
+
         // After emitting (and advancing), wait for more audio before running Whisper again,
         // unless flushing (finish() will call this again until no progress).
         if !force_flush {
@@ -199,6 +216,81 @@ impl<'a> BufferedSegmentTranscriber<'a> {
 
         Ok(Progress::Advanced)
     }
+
+    /// Report the uncommitted tail of the current hypothesis as provisional output.
+    ///
+    /// No-op unless `Opts::enable_partial_results` is set or when nothing is pending.
+    fn write_partial(&mut self, tail: &[WindowSegment], offset_seconds: f32) -> Result<()> {
+        if !self.opts.enable_partial_results || tail.is_empty() {
+            return Ok(());
+        }
+
+        let segments: Vec<Segment> = tail
+            .iter()
+            .map(|window_segment| {
+                let mut segment = window_segment.segment.clone();
+                segment.shift_by(offset_seconds);
+                segment
+            })
+            .collect();
+
+        self.encoder
+            .write_partial(&segments)
+            .map_err(anyhow::Error::new)?;
+        Ok(())
+    }
+
+    /// Split a hypothesis into the part that is final under the configured policy and the
+    /// provisional rest.
+    ///
+    /// For `LocalAgreement`, also records the hypothesis so later passes can agree with it.
+    fn split_committed(
+        &mut self,
+        mut hypothesis: Vec<WindowSegment>,
+    ) -> (Vec<WindowSegment>, Vec<WindowSegment>) {
+        match self.opts.incremental_commit_policy {
+            CommitPolicy::AllButLast => {
+                let pending = hypothesis.split_off(hypothesis.len().saturating_sub(1));
+                (hypothesis, pending)
+            }
+            CommitPolicy::LocalAgreement { n } => {
+                let words: Vec<String> = hypothesis
+                    .iter()
+                    .flat_map(|window_segment| segment_words(&window_segment.segment))
+                    .collect();
+
+                let required_previous = n.max(2) - 1;
+                let agreed = if self.history.len() >= required_previous {
+                    self.history
+                        .iter()
+                        .rev()
+                        .take(required_previous)
+                        .map(|previous| common_prefix_len(previous, &words))
+                        .min()
+                        .unwrap_or(0)
+                } else {
+                    0
+                };
+
+                self.history.push_back(words);
+                while self.history.len() > required_previous {
+                    self.history.pop_front();
+                }
+
+                split_at_word(hypothesis, agreed)
+            }
+        }
+    }
+
+    fn record_no_progress(&mut self, win_len: usize) {
+        self.no_progress_runs = self.no_progress_runs.saturating_add(1);
+        self.next_infer_at_samples = next_infer_threshold(
+            win_len,
+            self.min_window_samples,
+            self.max_window_samples,
+            self.no_progress_runs,
+        );
+    }
 }
 
 impl SamplesSink for BufferedSegmentTranscriber<'_> {
@@ -215,6 +307,112 @@ enum Progress {
     Advanced,
 }
 
+/// The words of a segment, as compared across passes by `LocalAgreement`.
+///
+/// Uses the timed words when the backend produced them and falls back to splitting the text, so
+/// whitespace differences are never treated as revisions.
+fn segment_words(segment: &Segment) -> Vec<String> {
+    if segment.words.is_empty() {
+        segment.text.split_whitespace().map(str::to_owned).collect()
+    } else {
+        segment.words.iter().map(|word| word.text.clone()).collect()
+    }
+}
+
+/// Split a hypothesis after its first `word_count` words.
+///
+/// Segments fully inside the prefix are committed whole. A segment the prefix ends inside is cut
+/// at the word boundary when it has word timings; without them it stays pending, since there is
+/// no timestamp to advance the window to.
+fn split_at_word(
+    hypothesis: Vec<WindowSegment>,
+    word_count: usize,
+) -> (Vec<WindowSegment>, Vec<WindowSegment>) {
+    let mut committed = Vec::new();
+    let mut pending = Vec::new();
+    let mut remaining = word_count;
+
+    for window_segment in hypothesis {
+        if remaining == 0 || !pending.is_empty() {
+            pending.push(window_segment);
+            continue;
+        }
+        let words = segment_words(&window_segment.segment).len();
+        if words <= remaining {
+            remaining -= words;
+            committed.push(window_segment);
+            continue;
+        }
+        match split_segment(&window_segment, remaining) {
+            Some((head, tail)) => {
+                committed.push(head);
+                pending.push(tail);
+            }
+            None => pending.push(window_segment),
+        }
+        remaining = 0;
+    }
+
+    (committed, pending)
+}
+
+/// Cut a segment with word timings into its first `word_count` words and the rest.
+fn split_segment(
+    window_segment: &WindowSegment,
+    word_count: usize,
+) -> Option<(WindowSegment, WindowSegment)> {
+    let segment = &window_segment.segment;
+    if word_count == 0 || word_count >= segment.words.len() {
+        return None;
+    }
+    let (head_words, tail_words) = segment.words.split_at(word_count);
+    let head_end = head_words[head_words.len() - 1].end_seconds;
+    let tail_start = tail_words[0].start_seconds;
+
+    // Keep the backend's leading-space convention so re-joined text reads the same.
+    let lead = if segment.text.starts_with(char::is_whitespace) {
+        " "
+    } else {
+        ""
+    };
+    let join = |words: &[Word]| {
+        let texts: Vec<&str> = words.iter().map(|word| word.text.as_str()).collect();
+        format!("{lead}{}", texts.join(" "))
+    };
+    let (head_tokens, tail_tokens) = segment
+        .tokens
+        .iter()
+        .cloned()
+        .partition(|token| token.start_seconds < tail_start);
+
+    let head = WindowSegment {
+        segment: Segment {
+            end_seconds: head_end,
+            text: join(head_words),
+            tokens: head_tokens,
+            words: head_words.to_vec(),
+            next_speaker_turn: false,
+            ..segment.clone()
+        },
+        end_timestamp_cs: (head_end * 100.0).round() as i64,
+    };
+    let tail = WindowSegment {
+        segment: Segment {
+            start_seconds: tail_start,
+            text: join(tail_words),
+            tokens: tail_tokens,
+            words: tail_words.to_vec(),
+            ..segment.clone()
+        },
+        end_timestamp_cs: window_segment.end_timestamp_cs,
+    };
+    Some((head, tail))
+}
+
+fn common_prefix_len(a: &[String], b: &[String]) -> usize {
+    a.iter().zip(b).take_while(|(x, y)| x == y).count()
+}
+
 fn next_infer_threshold(
     current_len: usize,
     min_window_samples: usize,
@@ -251,11 +449,301 @@ fn segment_end_samples(end_timestamp_cs: i64, available_samples: usize) -> Resul
     Ok(end_samples)
 }
 
-fn apply_time_offset(segment: &mut crate::segments::Segment, offset_seconds: f32) {
-    segment.start_seconds += offset_seconds;
-    segment.end_seconds += offset_seconds;
-    for token in &mut segment.tokens {
-        token.start_seconds += offset_seconds;
-        token.end_seconds += offset_seconds;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    type ScriptedPass = Vec<(&'static str, f32, f32)>;
+
+    /// Returns pre-programmed hypotheses, one per inference pass. Words are spread evenly over
+    /// each segment.
+    struct ScriptedInference {
+        passes: RefCell<VecDeque<ScriptedPass>>,
+    }
+
+    impl ScriptedInference {
+        fn new(passes: Vec<ScriptedPass>) -> Self {
+            Self {
+                passes: RefCell::new(passes.into()),
+            }
+        }
+    }
+
+    impl WindowInference for ScriptedInference {
+        fn infer(&self, _opts: &Opts, _samples: &[f32]) -> Result<Vec<WindowSegment>> {
+            let pass = self.passes.borrow_mut().pop_front().unwrap_or_default();
+            Ok(pass
+                .into_iter()
+                .map(|(text, start, end)| WindowSegment {
+                    segment: segment(text, start, end),
+                    end_timestamp_cs: (end * 100.0).round() as i64,
+                })
+                .collect())
+        }
+    }
+
+    fn segment(text: &str, start: f32, end: f32) -> Segment {
+        Segment {
+            start_seconds: start,
+            end_seconds: end,
+            text: text.to_owned(),
+            words: even_words(text, start, end),
+            ..Segment::default()
+        }
+    }
+
+    fn even_words(text: &str, start: f32, end: f32) -> Vec<Word> {
+        let texts: Vec<&str> = text.split_whitespace().collect();
+        let step = (end - start) / texts.len().max(1) as f32;
+        texts
+            .iter()
+            .enumerate()
+            .map(|(i, text)| Word {
+                start_seconds: start + step * i as f32,
+                end_seconds: start + step * (i + 1) as f32,
+                text: (*text).to_owned(),
+                probability: 1.0,
+            })
+            .collect()
+    }
+
+    /// Records emitted segments into a shared buffer the test can inspect mid-stream.
+    #[derive(Default)]
+    struct RecordingEncoder {
+        segments: Rc<RefCell<Vec<Segment>>>,
+        partials: Rc<RefCell<Vec<Vec<Segment>>>>,
+    }
+
+    impl SegmentEncoder for RecordingEncoder {
+        fn write_segment(&mut self, seg: &Segment) -> crate::Result<()> {
+            self.segments.borrow_mut().push(seg.clone());
+            Ok(())
+        }
+
+        fn write_partial(&mut self, segments: &[Segment]) -> crate::Result<()> {
+            self.partials.borrow_mut().push(segments.to_vec());
+            Ok(())
+        }
+
+        fn close(&mut self) -> crate::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn opts(policy: CommitPolicy, max_buffer_seconds: usize) -> Opts {
+        Opts {
+            incremental_max_buffer_seconds: max_buffer_seconds,
+            incremental_commit_policy: policy,
+            ..Opts::default()
+        }
+    }
+
+    fn one_second() -> Vec<f32> {
+        vec![0.0; TARGET_SAMPLE_RATE as usize]
+    }
+
+    fn texts(segments: &Rc<RefCell<Vec<Segment>>>) -> Vec<String> {
+        segments.borrow().iter().map(|s| s.text.clone()).collect()
+    }
+
+    #[test]
+    fn all_but_last_emits_unconfirmed_segments() -> Result<()> {
+        let ctx = ScriptedInference::new(vec![vec![("hello", 0.0, 0.5), ("wor", 0.5, 1.0)]]);
+        let opts = opts(CommitPolicy::AllButLast, 30);
+        let mut encoder = RecordingEncoder::default();
+        let emitted = Rc::clone(&encoder.segments);
+
+        let mut t = BufferedSegmentTranscriber::new(&ctx, &opts, &mut encoder);
+        t.on_samples(&one_second())?;
+
+        assert_eq!(texts(&emitted), vec!["hello"]);
+        Ok(())
+    }
+
+    #[test]
+    fn local_agreement_waits_for_consecutive_matching_hypotheses() -> Result<()> {
+        let ctx = ScriptedInference::new(vec![
+            vec![("hello", 0.0, 0.5), ("wor", 0.5, 1.0)],
+            vec![("hello", 0.0, 0.5), ("world", 0.5, 1.5), ("and", 1.5, 2.0)],
+            // The window now starts after "hello".
+            vec![("world", 0.0, 1.0), ("and more", 1.0, 2.5)],
+        ]);
+        let opts = opts(CommitPolicy::LocalAgreement { n: 2 }, 30);
+        let mut encoder = RecordingEncoder::default();
+        let emitted = Rc::clone(&encoder.segments);
+
+        let mut t = BufferedSegmentTranscriber::new(&ctx, &opts, &mut encoder);
+
+        // First pass has nothing to agree with.
+        t.on_samples(&one_second())?;
+        assert!(texts(&emitted).is_empty());
+
+        // Second pass agrees on "hello" only; "wor" was revised to "world".
+        t.on_samples(&one_second())?;
+        assert_eq!(texts(&emitted), vec!["hello"]);
+
+        // Third pass agrees with the remainder of the second on "world and"; "more" is new.
+        t.on_samples(&one_second())?;
+        assert_eq!(texts(&emitted), vec!["hello", "world", "and"]);
+        Ok(())
+    }
+
+    #[test]
+    fn local_agreement_commits_up_to_the_agreed_word_inside_a_segment() -> Result<()> {
+        let ctx = ScriptedInference::new(vec![
+            vec![(" ask not what", 0.0, 1.5)],
+            // Same words, but the last one was revised and the segmentation changed.
+            vec![(" ask not whether", 0.0, 1.5), (" you can", 1.5, 2.0)],
+            vec![(" whether you can do", 0.0, 2.0)],
+        ]);
+        let mut opts = opts(CommitPolicy::LocalAgreement { n: 2 }, 30);
+        opts.enable_partial_results = true;
+        let mut encoder = RecordingEncoder::default();
+        let emitted = Rc::clone(&encoder.segments);
+        let partials = Rc::clone(&encoder.partials);
+
+        let mut t = BufferedSegmentTranscriber::new(&ctx, &opts, &mut encoder);
+        t.on_samples(&one_second())?;
+        t.on_samples(&one_second())?;
+
+        // "ask not" is agreed although its segment continues with a revised word.
+        assert_eq!(texts(&emitted), vec![" ask not"]);
+        assert_eq!(emitted.borrow()[0].end_seconds, 1.0);
+        assert_eq!(emitted.borrow()[0].words.len(), 2);
+        let last = partials.borrow().last().cloned().unwrap_or_default();
+        let pending: Vec<_> = last.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(pending, [" whether", " you can"]);
+        assert_eq!(last[0].start_seconds, 1.0);
+
+        // The window now starts after "not", so the next pass agrees on "whether you can".
+        t.on_samples(&one_second())?;
+        assert_eq!(texts(&emitted), vec![" ask not", " whether you can"]);
+        assert_eq!(emitted.borrow()[1].start_seconds, 1.0);
+        Ok(())
+    }
+
+    #[test]
+    fn split_at_word_keeps_untimed_segments_whole() {
+        let untimed = |text: &str| WindowSegment {
+            segment: Segment {
+                words: Vec::new(),
+                ..segment(text, 0.0, 1.0)
+            },
+            end_timestamp_cs: 100,
+        };
+        let (committed, pending) = split_at_word(vec![untimed("one"), untimed("two three")], 2);
+        assert_eq!(committed.len(), 1);
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].segment.text, "two three");
+    }
+
+    #[test]
+    fn local_agreement_offsets_committed_segments_by_advanced_audio() -> Result<()> {
+        let ctx = ScriptedInference::new(vec![
+            vec![("one", 0.0, 1.0), ("tw", 1.0, 1.5)],
+            vec![("one", 0.0, 1.0), ("two", 1.0, 2.0)],
+            vec![("two", 0.0, 1.0), ("three", 1.0, 2.0)],
+        ]);
+        let opts = opts(CommitPolicy::LocalAgreement { n: 2 }, 30);
+        let mut encoder = RecordingEncoder::default();
+        let emitted = Rc::clone(&encoder.segments);
+
+        let mut t = BufferedSegmentTranscriber::new(&ctx, &opts, &mut encoder);
+        t.on_samples(&one_second())?;
+        t.on_samples(&one_second())?;
+        t.on_samples(&one_second())?;
+
+        assert_eq!(texts(&emitted), vec!["one", "two"]);
+        assert_eq!(emitted.borrow()[1].start_seconds, 1.0);
+        Ok(())
+    }
+
+    #[test]
+    fn max_buffer_forces_flush_without_agreement() -> Result<()> {
+        let ctx = ScriptedInference::new(vec![
+            vec![("a", 0.0, 0.5)],
+            vec![("b", 0.0, 0.5), ("c", 0.5, 2.0)],
+        ]);
+        let opts = opts(CommitPolicy::LocalAgreement { n: 3 }, 2);
+        let mut encoder = RecordingEncoder::default();
+        let emitted = Rc::clone(&encoder.segments);
+
+        let mut t = BufferedSegmentTranscriber::new(&ctx, &opts, &mut encoder);
+        t.on_samples(&one_second())?;
+        assert!(texts(&emitted).is_empty());
+        t.on_samples(&one_second())?;
+
+        assert_eq!(texts(&emitted), vec!["b", "c"]);
+        Ok(())
+    }
+
+    #[test]
+    fn finish_flushes_pending_hypothesis() -> Result<()> {
+        let ctx = ScriptedInference::new(vec![vec![("tail", 0.0, 0.5)]]);
+        let opts = opts(CommitPolicy::LocalAgreement { n: 2 }, 30);
+        let mut encoder = RecordingEncoder::default();
+        let emitted = Rc::clone(&encoder.segments);
+
+        let mut t = BufferedSegmentTranscriber::new(&ctx, &opts, &mut encoder);
+        t.on_samples(&[0.0; 100])?;
+        t.finish()?;
+
+        assert_eq!(texts(&emitted), vec!["tail"]);
+        Ok(())
+    }
+
+    #[test]
+    fn partial_results_report_uncommitted_tail_each_pass() -> Result<()> {
+        let ctx = ScriptedInference::new(vec![
+            vec![("one", 0.0, 1.0), ("tw", 1.0, 1.5)],
+            vec![("one", 0.0, 1.0), ("two", 1.0, 2.0)],
+        ]);
+        let mut opts = opts(CommitPolicy::LocalAgreement { n: 2 }, 30);
+        opts.enable_partial_results = true;
+        let mut encoder = RecordingEncoder::default();
+        let emitted = Rc::clone(&encoder.segments);
+        let partials = Rc::clone(&encoder.partials);
+
+        let mut t = BufferedSegmentTranscriber::new(&ctx, &opts, &mut encoder);
+
+        // Nothing is final yet, so the whole hypothesis is provisional.
+        t.on_samples(&one_second())?;
+        assert!(texts(&emitted).is_empty());
+        assert_eq!(partials.borrow().len(), 1);
+        assert_eq!(partials.borrow()[0].len(), 2);
+
+        // "one" commits; only "two" remains provisional.
+        t.on_samples(&one_second())?;
+        assert_eq!(texts(&emitted), vec!["one"]);
+        let last = partials.borrow().last().cloned().unwrap_or_default();
+        assert_eq!(last.len(), 1);
+        assert_eq!(last[0].text, "two");
+        assert_eq!(last[0].start_seconds, 1.0);
+        Ok(())
+    }
+
+    #[test]
+    fn partial_results_are_off_by_default() -> Result<()> {
+        let ctx = ScriptedInference::new(vec![vec![("hello", 0.0, 0.5), ("wor", 0.5, 1.0)]]);
+        let opts = opts(CommitPolicy::AllButLast, 30);
+        let mut encoder = RecordingEncoder::default();
+        let partials = Rc::clone(&encoder.partials);
+
+        let mut t = BufferedSegmentTranscriber::new(&ctx, &opts, &mut encoder);
+        t.on_samples(&one_second())?;
+
+        assert!(partials.borrow().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn segment_words_ignore_whitespace_without_word_timings() {
+        let untimed = Segment {
+            words: Vec::new(),
+            ..segment("  hello   world ", 0.0, 1.0)
+        };
+        assert_eq!(segment_words(&untimed), ["hello", "world"]);
     }
 }
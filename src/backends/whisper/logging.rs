@@ -1,13 +1,87 @@
+use std::cell::RefCell;
+use std::ffi::CStr;
 use std::os::raw::{c_char, c_void};
 use std::sync::Once;
 
-/// A no-op log callback used to silence logs emitted by whisper.cpp.
+// ggml log levels (`enum ggml_log_level`); whisper.cpp routes its own and ggml's messages
+// through the same callback.
+const GGML_LOG_LEVEL_DEBUG: u32 = 1;
+const GGML_LOG_LEVEL_INFO: u32 = 2;
+const GGML_LOG_LEVEL_WARN: u32 = 3;
+const GGML_LOG_LEVEL_ERROR: u32 = 4;
+const GGML_LOG_LEVEL_CONT: u32 = 5;
+
+thread_local! {
+    static PENDING: RefCell<LineBuffer> = RefCell::new(LineBuffer::default());
+}
+
+/// Reassembles log lines that whisper.cpp emits in several calls.
+///
+/// Messages arrive as fragments: a line may be split across calls without a trailing newline,
+/// and `GGML_LOG_LEVEL_CONT` continues the previous message at its level.
+#[derive(Default)]
+struct LineBuffer {
+    level: u32,
+    text: String,
+}
+
+impl LineBuffer {
+    /// Append a fragment and return every line it completes, with its level.
+    fn push(&mut self, level: u32, fragment: &str) -> Vec<(u32, String)> {
+        let mut lines = Vec::new();
+
+        if level != GGML_LOG_LEVEL_CONT {
+            // A new message starts; flush any unterminated one so it is not lost.
+            lines.extend(self.take());
+            self.level = level;
+        }
+
+        for piece in fragment.split_inclusive('\n') {
+            self.text.push_str(piece);
+            if piece.ends_with('\n') {
+                lines.extend(self.take());
+            }
+        }
+        lines
+    }
+
+    fn take(&mut self) -> Option<(u32, String)> {
+        let text = std::mem::take(&mut self.text);
+        let line = text.trim_end();
+        (!line.is_empty()).then(|| (self.level, line.to_owned()))
+    }
+}
+
+fn emit(level: u32, message: &str) {
+    match level {
+        GGML_LOG_LEVEL_ERROR => tracing::error!(target: "scribble::whisper", "{message}"),
+        GGML_LOG_LEVEL_WARN => tracing::warn!(target: "scribble::whisper", "{message}"),
+        GGML_LOG_LEVEL_INFO => tracing::info!(target: "scribble::whisper", "{message}"),
+        GGML_LOG_LEVEL_DEBUG => tracing::debug!(target: "scribble::whisper", "{message}"),
+        _ => tracing::trace!(target: "scribble::whisper", "{message}"),
+    }
+}
+
+/// Forward whisper.cpp/ggml log messages to `tracing` under the `scribble::whisper` target.
 unsafe extern "C" fn whisper_log_callback(
-    _level: u32,
-    _c_msg: *const c_char,
+    level: u32,
+    c_msg: *const c_char,
     _user_data: *mut c_void,
 ) {
-    // Intentionally left empty.
+    if c_msg.is_null() {
+        return;
+    }
+
+    // SAFETY: whisper.cpp passes a valid NUL-terminated string for the duration of the call.
+    let fragment = unsafe { CStr::from_ptr(c_msg) }.to_string_lossy();
+
+    // Never unwind across the FFI boundary.
+    let _ = std::panic::catch_unwind(|| {
+        let lines = PENDING.with(|pending| pending.borrow_mut().push(level, &fragment));
+        for (level, line) in lines {
+            emit(level, &line);
+        }
+    });
 }
 
 /// Ensure whisper logging is configured exactly once for the lifetime of the process.
@@ -18,3 +92,35 @@ pub fn init_whisper_logging() {
         whisper_rs::set_log_callback(Some(whisper_log_callback), std::ptr::null_mut());
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_buffer_joins_fragments_and_continuations() {
+        let mut buffer = LineBuffer::default();
+
+        assert!(buffer.push(GGML_LOG_LEVEL_INFO, "loading model").is_empty());
+        assert!(buffer.push(GGML_LOG_LEVEL_CONT, "...").is_empty());
+        assert_eq!(
+            buffer.push(GGML_LOG_LEVEL_CONT, " done\n"),
+            vec![(GGML_LOG_LEVEL_INFO, "loading model... done".to_owned())]
+        );
+    }
+
+    #[test]
+    fn line_buffer_flushes_unterminated_message_on_new_level() {
+        let mut buffer = LineBuffer::default();
+
+        assert!(buffer.push(GGML_LOG_LEVEL_WARN, "no GPU found").is_empty());
+        assert_eq!(
+            buffer.push(GGML_LOG_LEVEL_ERROR, "failed to load\nretrying\n"),
+            vec![
+                (GGML_LOG_LEVEL_WARN, "no GPU found".to_owned()),
+                (GGML_LOG_LEVEL_ERROR, "failed to load".to_owned()),
+                (GGML_LOG_LEVEL_ERROR, "retrying".to_owned()),
+            ]
+        );
+    }
+}
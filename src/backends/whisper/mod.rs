@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::ops::Range;
 use std::path::Path;
 
 use anyhow::{Result as AnyResult, anyhow, ensure};
@@ -6,17 +7,21 @@ use whisper_rs::WhisperContext;
 
 use crate::Result;
 use crate::backend::{Backend, BackendStream};
+use crate::capabilities::Capabilities;
 use crate::decoder::SamplesSink;
 use crate::opts::Opts;
 use crate::segment_encoder::SegmentEncoder;
+use crate::transcription_mode::TranscriptionMode;
 
 mod ctx;
 mod incremental;
 mod logging;
+mod parallel;
 mod segments;
 mod token;
 
 use incremental::BufferedSegmentTranscriber;
+use parallel::emit_chunk_segments;
 use segments::emit_segments;
 
 /// Built-in backend powered by `whisper-rs` / `whisper.cpp`.
@@ -171,12 +176,15 @@ impl WhisperBackend {
 
     fn selected_context<'a>(&'a self, opts: &'a Opts) -> AnyResult<&'a WhisperContext> {
         let key = self.selected_model_key(opts)?;
+        self.context_for_key(key)
+            .ok_or_else(|| anyhow!("selected model '{key}' was not loaded"))
+    }
+
+    fn context_for_key(&self, key: &str) -> Option<&WhisperContext> {
         if key == self.first_model_key {
-            return Ok(&self.first_model);
+            return Some(&self.first_model);
         }
-        self.models
-            .get(key)
-            .ok_or_else(|| anyhow!("selected model '{key}' was not loaded"))
+        self.models.get(key)
     }
 
     fn available_model_keys(&self) -> String {
@@ -203,6 +211,19 @@ impl Backend for WhisperBackend {
             .map_err(Into::into)
     }
 
+    /// Transcribes chunks concurrently, one `WhisperState` per chunk, using the worker count from
+    /// `TranscriptionMode::ParallelChunks` (sequentially otherwise).
+    fn transcribe_chunks(
+        &self,
+        opts: &Opts,
+        encoder: &mut dyn SegmentEncoder,
+        samples: &[f32],
+        chunks: &[Range<usize>],
+    ) -> Result<()> {
+        self.transcribe_chunks_anyhow(opts, encoder, samples, chunks)
+            .map_err(Into::into)
+    }
+
     fn create_stream<'a>(
         &'a self,
         opts: &'a Opts,
@@ -210,6 +231,34 @@ impl Backend for WhisperBackend {
     ) -> Result<Self::Stream<'a>> {
         self.create_stream_anyhow(opts, encoder).map_err(Into::into)
     }
+
+    /// English-only models (`*.en`) cannot translate and only accept `"en"`; multilingual models
+    /// accept every language whisper.cpp knows.
+    ///
+    /// Unknown keys report the defaults; transcription then fails with the unknown-key error.
+    fn capabilities(&self, model_key: Option<&str>) -> Capabilities {
+        let key = model_key.unwrap_or(&self.first_model_key);
+        let Some(ctx) = self.context_for_key(key) else {
+            return Capabilities::default();
+        };
+
+        let multilingual = ctx.is_multilingual();
+        let languages = if multilingual {
+            (0..=whisper_rs::get_lang_max_id())
+                .filter_map(whisper_rs::get_lang_str)
+                .map(str::to_owned)
+                .collect()
+        } else {
+            vec!["en".to_owned()]
+        };
+
+        Capabilities {
+            translation: multilingual,
+            streaming: true,
+            word_timestamps: true,
+            languages: Some(languages),
+        }
+    }
 }
 
 impl WhisperBackend {
@@ -232,6 +281,24 @@ impl WhisperBackend {
         })
     }
 
+    fn transcribe_chunks_anyhow(
+        &self,
+        opts: &Opts,
+        encoder: &mut dyn SegmentEncoder,
+        samples: &[f32],
+        chunks: &[Range<usize>],
+    ) -> AnyResult<()> {
+        let ctx = self.selected_context(opts)?;
+        let workers = match opts.transcription_mode {
+            TranscriptionMode::ParallelChunks { workers, .. } => workers,
+            TranscriptionMode::Streaming | TranscriptionMode::Full { .. } => 1,
+        };
+
+        emit_chunk_segments(ctx, opts, samples, chunks, workers, &mut |seg| {
+            encoder.write_segment(seg).map_err(Into::into)
+        })
+    }
+
     fn create_stream_anyhow<'a>(
         &'a self,
         opts: &'a Opts,
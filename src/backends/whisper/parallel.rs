@@ -0,0 +1,187 @@
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+use anyhow::Result;
+use whisper_rs::WhisperContext;
+
+use crate::audio_pipeline::TARGET_SAMPLE_RATE;
+use crate::opts::Opts;
+use crate::segments::Segment;
+
+use super::segments::{run_whisper_full, to_segment};
+
+type ChunkResult = (usize, Result<Vec<Segment>>);
+
+/// Transcribe `chunks` of `samples` on up to `workers` whisper states at once.
+///
+/// Each chunk gets its own `WhisperState`; the CPU is divided between concurrent passes so they
+/// don't oversubscribe cores. Segments reach `on_segment` in chunk order, shifted onto the
+/// timeline of `samples`.
+pub(super) fn emit_chunk_segments(
+    ctx: &WhisperContext,
+    opts: &Opts,
+    samples: &[f32],
+    chunks: &[Range<usize>],
+    workers: usize,
+    on_segment: &mut dyn FnMut(&Segment) -> Result<()>,
+) -> Result<()> {
+    let workers = workers.clamp(1, chunks.len().max(1));
+    let n_threads = (num_cpus::get() / workers).max(1);
+
+    run_in_order(
+        chunks,
+        workers,
+        |chunk| transcribe_chunk(ctx, opts, samples, chunk, n_threads),
+        on_segment,
+    )
+}
+
+fn transcribe_chunk(
+    ctx: &WhisperContext,
+    opts: &Opts,
+    samples: &[f32],
+    chunk: Range<usize>,
+    n_threads: usize,
+) -> Result<Vec<Segment>> {
+    let offset_seconds = chunk.start as f32 / TARGET_SAMPLE_RATE as f32;
+    let state = run_whisper_full(ctx, opts, &samples[chunk], n_threads)?;
+    state
+        .as_iter()
+        .map(|whisper_segment| {
+            let mut segment = to_segment(whisper_segment, opts)?;
+            segment.shift_by(offset_seconds);
+            Ok(segment)
+        })
+        .collect()
+}
+
+/// Run `transcribe` over `chunks` on a pool of scoped threads.
+///
+/// Workers pull the next chunk index from a shared counter, so long chunks don't hold up the
+/// rest of the queue. Results are buffered until every earlier chunk is done, then handed to
+/// `on_segment`; output therefore starts as soon as the first chunk finishes. The first error
+/// stops workers from taking new chunks and is returned once in-flight chunks finish.
+fn run_in_order<F>(
+    chunks: &[Range<usize>],
+    workers: usize,
+    transcribe: F,
+    on_segment: &mut dyn FnMut(&Segment) -> Result<()>,
+) -> Result<()>
+where
+    F: Fn(Range<usize>) -> Result<Vec<Segment>> + Sync,
+{
+    let next_chunk = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+    let (tx, rx) = mpsc::channel::<ChunkResult>();
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            let tx = tx.clone();
+            let (next_chunk, stop, transcribe) = (&next_chunk, &stop, &transcribe);
+            scope.spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    let index = next_chunk.fetch_add(1, Ordering::Relaxed);
+                    let Some(chunk) = chunks.get(index) else {
+                        break;
+                    };
+                    if tx.send((index, transcribe(chunk.clone()))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(tx);
+
+        let res = emit_in_order(&rx, on_segment);
+        if res.is_err() {
+            stop.store(true, Ordering::Relaxed);
+        }
+        res
+    })
+}
+
+fn emit_in_order(
+    rx: &mpsc::Receiver<ChunkResult>,
+    on_segment: &mut dyn FnMut(&Segment) -> Result<()>,
+) -> Result<()> {
+    let mut ready = BTreeMap::new();
+    let mut next_emit = 0usize;
+
+    for (index, res) in rx {
+        ready.insert(index, res?);
+        while let Some(segments) = ready.remove(&next_emit) {
+            for segment in &segments {
+                on_segment(segment)?;
+            }
+            next_emit += 1;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn seg(text: String) -> Segment {
+        Segment {
+            start_seconds: 0.0,
+            end_seconds: 0.0,
+            text,
+            ..Segment::default()
+        }
+    }
+
+    #[test]
+    fn run_in_order_emits_chunks_in_order_despite_completion_order() -> Result<()> {
+        let chunks: Vec<Range<usize>> = (0..6).map(|i| i..i + 1).collect();
+        let mut texts = Vec::new();
+
+        run_in_order(
+            &chunks,
+            3,
+            |chunk| {
+                // Earlier chunks finish last.
+                thread::sleep(Duration::from_millis(5 * (6 - chunk.start) as u64));
+                Ok(vec![
+                    seg(format!("{}a", chunk.start)),
+                    seg(format!("{}b", chunk.start)),
+                ])
+            },
+            &mut |segment| {
+                texts.push(segment.text.clone());
+                Ok(())
+            },
+        )?;
+
+        let expected: Vec<String> = (0..6)
+            .flat_map(|i| [format!("{i}a"), format!("{i}b")])
+            .collect();
+        assert_eq!(texts, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn run_in_order_surfaces_chunk_errors() {
+        let chunks: Vec<Range<usize>> = (0..4).map(|i| i..i + 1).collect();
+
+        let err = run_in_order(
+            &chunks,
+            2,
+            |chunk| {
+                if chunk.start == 1 {
+                    anyhow::bail!("chunk failed");
+                }
+                Ok(vec![seg(chunk.start.to_string())])
+            },
+            &mut |_| Ok(()),
+        )
+        .unwrap_err();
+
+        assert!(err.to_string().contains("chunk failed"));
+    }
+}
@@ -3,8 +3,9 @@ use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperSegment, W
 
 use crate::opts::Opts;
 use crate::segments::Segment;
-use crate::token::centiseconds_to_seconds;
+use crate::token::{Token, centiseconds_to_seconds, group_words};
 
+use super::incremental::{WindowInference, WindowSegment};
 use super::token::tokens_from_segment;
 
 /// Our current placeholder language code.
@@ -19,47 +20,80 @@ pub(super) fn emit_segments(
     samples: &[f32],
     on_segment: &mut dyn FnMut(&Segment) -> Result<()>,
 ) -> Result<()> {
-    let state = run_whisper_full(ctx, opts, samples)?;
+    let state = run_whisper_full(ctx, opts, samples, num_cpus::get())?;
     for whisper_segment in state.as_iter() {
-        let segment = to_segment(whisper_segment)?;
+        let segment = to_segment(whisper_segment, opts)?;
         on_segment(&segment)?;
     }
     Ok(())
 }
 
-pub(super) fn to_segment(segment: WhisperSegment) -> Result<Segment> {
+pub(super) fn to_segment(segment: WhisperSegment, opts: &Opts) -> Result<Segment> {
     let text = segment
         .to_str()
         .context("failed to get segment text")?
         .to_owned();
 
-    let tokens = tokens_from_segment(&segment)?;
+    let mut tokens = tokens_from_segment(&segment)?;
 
     // Prefer token-derived timing when available to avoid long segments that include
     // leading/trailing silence. Fall back to whisper’s segment-level timestamps when token
     // timing is unavailable.
     let (start_seconds, end_seconds) = segment_seconds_from_tokens_or_fallback(&segment, &tokens);
 
+    let avg_logprob = avg_logprob(&tokens);
+    let no_speech_probability = segment.no_speech_probability();
+    let has_text = tokens.iter().any(Token::is_text);
+    let words = group_words(&tokens);
+
+    // Timestamp and control tokens are only useful to callers debugging the decoder; keep them
+    // out of the output unless asked for.
+    if !opts.enable_non_text_tokens {
+        tokens.retain(Token::is_text);
+    }
+
     Ok(Segment {
         start_seconds,
         end_seconds,
         text,
         tokens,
+        words,
         language_code: DEFAULT_LANGUAGE_CODE.to_owned(),
         next_speaker_turn: segment.next_segment_speaker_turn(),
+        avg_logprob,
+        no_speech_probability,
+        confidence: Segment::confidence_from(avg_logprob, no_speech_probability, has_text),
+        from_fallback: false,
+        channel: None,
     })
 }
 
+/// Mean log-probability of the text tokens; control tokens carry no information about the
+/// transcription and are skipped.
+fn avg_logprob(tokens: &[Token]) -> f32 {
+    let logprobs: Vec<f32> = tokens
+        .iter()
+        .filter(|token| token.is_text())
+        // Guard against `ln(0) = -inf`, which would poison the mean and serialize as `null`.
+        .map(|token| token.probability.max(f32::MIN_POSITIVE).ln())
+        .collect();
+
+    if logprobs.is_empty() {
+        return 0.0;
+    }
+    logprobs.iter().sum::<f32>() / logprobs.len() as f32
+}
+
 fn segment_seconds_from_tokens_or_fallback(
     segment: &WhisperSegment,
-    tokens: &[crate::token::Token],
+    tokens: &[Token],
 ) -> (f32, f32) {
     let mut min_start: Option<f32> = None;
     let mut max_end: Option<f32> = None;
 
     for token in tokens {
-        // Filter out whisper special/control tokens (commonly formatted like `[_BEG_]`, `[_TT_50]`).
-        if token.text.starts_with("[_") && token.text.ends_with("_]") {
+        // Filter out whisper timestamp and control tokens (formatted like `[_BEG_]`, `[_TT_50]`).
+        if !token.is_text() {
             continue;
         }
 
@@ -81,13 +115,13 @@ fn segment_seconds_from_tokens_or_fallback(
     }
 }
 
-fn build_full_params(opts: &Opts) -> FullParams<'_, '_> {
+fn build_full_params(opts: &Opts, n_threads: usize) -> FullParams<'_, '_> {
     let mut params = FullParams::new(SamplingStrategy::BeamSearch {
         beam_size: 5,
         patience: 1.0,
     });
 
-    params.set_n_threads(num_cpus::get() as i32);
+    params.set_n_threads(n_threads as i32);
     params.set_translate(opts.enable_translate_to_english);
     params.set_language(opts.language.as_deref());
     params.set_no_context(true);
@@ -100,23 +134,62 @@ fn build_full_params(opts: &Opts) -> FullParams<'_, '_> {
 
     params.set_token_timestamps(true);
 
+    // whisper.cpp splits segments by length when `max_len` is set; a length of 1 combined with
+    // word splitting yields exactly one word per segment without post-hoc token grouping.
+    if opts.enable_word_level_segments {
+        params.set_max_len(1);
+        params.set_split_on_word(true);
+    }
+
+    if let Some(listener) = opts.progress_listener.clone() {
+        params.set_progress_callback_safe(move |percent| listener.on_backend_progress(percent));
+    }
+
+    if let Some(token) = opts.cancellation_token.clone() {
+        // `whisper-rs` reinterprets the boxed closure as `F`, so pass an already-boxed closure
+        // to keep the pointer types consistent.
+        let abort: Box<dyn FnMut() -> bool> = Box::new(move || token.is_cancelled());
+        params.set_abort_callback_safe(abort);
+    }
+
     params
 }
 
+impl WindowInference for WhisperContext {
+    fn infer(&self, opts: &Opts, samples: &[f32]) -> Result<Vec<WindowSegment>> {
+        let state = run_whisper_full(self, opts, samples, num_cpus::get())?;
+        state
+            .as_iter()
+            .map(|whisper_segment| {
+                let end_timestamp_cs = whisper_segment.end_timestamp();
+                Ok(WindowSegment {
+                    segment: to_segment(whisper_segment, opts)?,
+                    end_timestamp_cs,
+                })
+            })
+            .collect()
+    }
+}
+
 pub(super) fn run_whisper_full(
     ctx: &WhisperContext,
     opts: &Opts,
     samples: &[f32],
+    n_threads: usize,
 ) -> Result<WhisperState> {
-    let params = build_full_params(opts);
+    let params = build_full_params(opts, n_threads);
 
     let mut state = ctx
         .create_state()
         .context("failed to create whisper state")?;
 
-    state
-        .full(params, samples)
-        .context("failed to run whisper full()")?;
+    let full_res = state.full(params, samples);
+    if opts.is_cancelled() {
+        // An abort requested via the callback surfaces as a generic whisper error; report it
+        // as a cancellation instead.
+        return Err(crate::Error::Cancelled.into());
+    }
+    full_res.context("failed to run whisper full()")?;
 
     Ok(state)
 }
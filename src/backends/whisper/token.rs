@@ -1,7 +1,7 @@
 use anyhow::{Context, Result};
 use whisper_rs::WhisperSegment;
 
-use crate::token::{Token, centiseconds_to_seconds};
+use crate::token::{Token, TokenKind, centiseconds_to_seconds};
 
 pub(super) fn tokens_from_segment(segment: &WhisperSegment) -> Result<Vec<Token>> {
     let token_count = segment.n_tokens();
@@ -25,6 +25,7 @@ pub(super) fn tokens_from_segment(segment: &WhisperSegment) -> Result<Vec<Token>
             // whisper uses -1 for unknown; clamp to 0 so consumers don't see -0.01s
             start_seconds: centiseconds_to_seconds(data.t0),
             end_seconds: centiseconds_to_seconds(data.t1),
+            kind: TokenKind::classify(&text),
             text,
             probability: data.p,
         });
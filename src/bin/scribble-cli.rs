@@ -1,12 +1,22 @@
 // src/bin/scribble-cli.rs
 
 use anyhow::{Context, Result};
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
 use std::fs::File;
-use std::io::{self, Read};
-use tracing::error;
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info};
 
-use scribble::{Opts, OutputType, Scribble};
+use scribble::{
+    CommitPolicy, HallucinationFilter, Opts, OutputType, Progress, ProgressListener,
+    RawAudioFormat, Scribble, TrackSelection, TranscriptionMode,
+};
+
+/// `--max-buffer-seconds` when the flag is not given; matches Whisper's native window.
+const DEFAULT_MAX_BUFFER_SECONDS: usize = 30;
 
 fn main() {
     scribble::init_logging();
@@ -19,6 +29,29 @@ fn main() {
 
 fn run() -> Result<()> {
     let params = Params::parse();
+    if let Some(Command::Probe { input }) = &params.command {
+        return probe(input);
+    }
+    // clap only lets these be omitted together with a subcommand.
+    let (Some(model_path), Some(vad_model_path), Some(input_path)) = (
+        params.model_path.clone(),
+        params.vad_model_path.clone(),
+        params.input.clone(),
+    ) else {
+        unreachable!("--model, --vad-model, and --input are required without a subcommand");
+    };
+
+    let progress = if params.no_progress {
+        None
+    } else {
+        Some(Arc::new(CliProgress::new()?))
+    };
+
+    let mode = transcription_mode(&params, is_regular_file(&input_path));
+    let hallucination_filter = hallucination_filter(&params);
+    let filter_stats = hallucination_filter
+        .as_ref()
+        .map(|filter| filter.stats.clone());
 
     // Map CLI flags into library options.
     //
@@ -31,45 +64,236 @@ fn run() -> Result<()> {
         enable_voice_activity_detection: params.enable_voice_activity_detection,
         language: params.language.clone(),
         output_type: params.output_type,
+        transcription_mode: mode,
         incremental_min_window_seconds: 1,
+        incremental_max_buffer_seconds: params
+            .max_buffer_seconds
+            .unwrap_or(DEFAULT_MAX_BUFFER_SECONDS),
+        incremental_commit_policy: commit_policy(params.local_agreement),
+        enable_partial_results: params.partial_results,
+        enable_word_level_segments: params.enable_word_level_segments,
+        enable_non_text_tokens: params.keep_non_text_tokens,
+        input_format: input_format(params.format.as_deref(), &input_path),
+        raw_input_format: params.raw_format,
+        audio_track: params.track.clone(),
+        enable_per_channel_transcription: params.split_channels,
+        start_seconds: params.start_seconds,
+        end_seconds: params.end_seconds,
+        max_audio_seconds: params.max_audio_seconds,
+        max_wall_clock_seconds: params.max_wall_clock_seconds,
+        hallucination_filter,
+        cancellation_token: None,
+        progress_listener: progress.clone().map(|p| p as Arc<dyn ProgressListener>),
     };
 
     // Open an input source.
-    // - File path → open directly.
-    // - "-"       → stream stdin.
+    // - Regular file → open directly; the decoder may seek (needed for some MP4/MOV layouts).
+    // - "-"          → stream stdin.
     //
     // Note: we pass `io::stdin()` (not `stdin().lock()`) to avoid non-Send lock guards.
-    let input = open_input(&params.input)?;
+    let input = open_input(&input_path)?;
 
     // Load the Whisper + VAD models (expensive).
     //
     // `Scribble::new` validates both model paths, so once this succeeds, we know the backend
     // is ready for repeated transcriptions.
-    let scribble = Scribble::new([params.model_path], params.vad_model_path)?;
+    let scribble = Scribble::new([model_path], vad_model_path)?;
 
     // Stream transcription output to stdout.
-    scribble
-        .transcribe(input, io::stdout(), &opts)
-        .context("transcription failed")?;
+    let res = match input {
+        Input::File(file) => scribble.transcribe_seekable(file, io::stdout(), &opts),
+        Input::Stream(reader) => scribble.transcribe(reader, io::stdout(), &opts),
+    }
+    .context("transcription failed");
+
+    if let Some(progress) = &progress {
+        progress.bar.finish_and_clear();
+    }
+
+    if let Some(counts) = filter_stats.map(|stats| stats.counts())
+        && counts.total() > 0
+    {
+        info!(
+            no_speech = counts.no_speech,
+            low_probability = counts.low_probability,
+            repetition = counts.repetition,
+            phrase = counts.phrase,
+            "filtered hallucinated segments"
+        );
+    }
+
+    res
+}
 
+/// Print container and audio track metadata for `path` as JSON on stdout.
+///
+/// The path's extension is passed as a container hint, as for transcription.
+fn probe(path: &str) -> Result<()> {
+    let hint = input_format(None, path);
+    let info = match open_input(path)? {
+        Input::File(file) => scribble::probe_seekable(file, hint.as_deref()),
+        Input::Stream(reader) => scribble::probe(reader, hint.as_deref()),
+    }
+    .context("probe failed")?;
+
+    let mut stdout = io::stdout().lock();
+    serde_json::to_writer_pretty(&mut stdout, &info).context("failed to write probe output")?;
+    writeln!(stdout)?;
     Ok(())
 }
 
-/// Open an input source as a boxed reader.
+/// Renders transcription progress as a progress bar on stderr.
+///
+/// Positions are tracked in milliseconds of source audio. Until the container reports a total
+/// duration (never, for e.g. stdin streams) the bar is a ticking spinner with elapsed audio time;
+/// it switches to a bar with a percentage and ETA once the total is known. indicatif hides the
+/// bar automatically when stderr is not a terminal.
+struct CliProgress {
+    bar: ProgressBar,
+    bar_style: ProgressStyle,
+}
+
+impl CliProgress {
+    fn new() -> Result<Self> {
+        let bar = ProgressBar::with_draw_target(None, ProgressDrawTarget::stderr());
+        bar.set_style(
+            ProgressStyle::with_template("{spinner:.green} {msg}")
+                .context("invalid progress spinner template")?,
+        );
+        bar.enable_steady_tick(Duration::from_millis(100));
+        let bar_style = ProgressStyle::with_template(
+            "{spinner:.green} {msg} {bar:40.cyan/blue} {percent}% (eta {eta})",
+        )
+        .context("invalid progress bar template")?
+        .progress_chars("#>-");
+        Ok(Self { bar, bar_style })
+    }
+}
+
+impl ProgressListener for CliProgress {
+    fn on_progress(&self, progress: &Progress) {
+        if let Some(total) = progress.total_seconds {
+            if self.bar.length().is_none() {
+                self.bar.set_style(self.bar_style.clone());
+            }
+            self.bar.set_length(seconds_to_ms(total));
+        }
+        self.bar
+            .set_position(seconds_to_ms(progress.transcribed_seconds));
+        self.bar.set_message(format!(
+            "transcribed {} / decoded {}",
+            format_clock(progress.transcribed_seconds),
+            format_clock(progress.decoded_seconds),
+        ));
+    }
+}
+
+fn seconds_to_ms(seconds: f64) -> u64 {
+    (seconds.max(0.0) * 1000.0).round() as u64
+}
+
+/// Format seconds as `HH:MM:SS` for progress messages.
+fn format_clock(seconds: f64) -> String {
+    let total = seconds.max(0.0) as u64;
+    format!(
+        "{:02}:{:02}:{:02}",
+        total / 3600,
+        (total / 60) % 60,
+        total % 60
+    )
+}
+
+/// Map the `--local-agreement` flag into a library commit policy.
+fn commit_policy(local_agreement: Option<usize>) -> CommitPolicy {
+    match local_agreement {
+        Some(n) => CommitPolicy::LocalAgreement { n },
+        None => CommitPolicy::AllButLast,
+    }
+}
+
+/// Pick a library transcription mode from the flags and the kind of input.
 ///
-/// We return `Box<dyn Read + Send>` because the decoder pipeline moves the reader to a
-/// dedicated decode thread.
+/// Regular files are bounded, so they default to a single full-buffer pass; stdin, pipes, and
+/// devices stream. `--parallel` and `--streaming` override the default, and the flags that only
+/// tune the incremental transcriber (`--local-agreement`, `--partial-results`,
+/// `--max-buffer-seconds`) imply `--streaming`.
+fn transcription_mode(params: &Params, input_is_regular_file: bool) -> TranscriptionMode {
+    if let Some(workers) = params.parallel {
+        return TranscriptionMode::ParallelChunks {
+            workers,
+            chunk_seconds: params.chunk_seconds,
+        };
+    }
+
+    let streaming = params.streaming
+        || params.local_agreement.is_some()
+        || params.partial_results
+        || params.max_buffer_seconds.is_some();
+    if input_is_regular_file && !streaming {
+        TranscriptionMode::Full {
+            max_seconds: params.max_full_seconds,
+        }
+    } else {
+        TranscriptionMode::Streaming
+    }
+}
+
+/// Build the hallucination filter requested by `--filter-hallucinations`, adding any extra
+/// `--hallucination-phrase` values to the default phrase list.
+fn hallucination_filter(params: &Params) -> Option<HallucinationFilter> {
+    if !params.filter_hallucinations {
+        return None;
+    }
+
+    let mut filter = HallucinationFilter::default();
+    filter
+        .phrases
+        .extend(params.hallucination_phrases.iter().cloned());
+    Some(filter)
+}
+
+/// The container hint for an input: `--format` if given, else the input path's extension.
+fn input_format(format: Option<&str>, path: &str) -> Option<String> {
+    let format = match format {
+        Some(format) => format,
+        None if path == "-" => return None,
+        None => Path::new(path).extension()?.to_str()?,
+    };
+    Some(format.trim_start_matches('.').to_ascii_lowercase())
+}
+
+/// Whether `path` names a regular file (not stdin, a FIFO, or a device).
+fn is_regular_file(path: &str) -> bool {
+    path != "-" && std::fs::metadata(path).is_ok_and(|meta| meta.is_file())
+}
+
+/// An opened input source.
+enum Input {
+    /// A regular file, decoded with seeking enabled.
+    File(File),
+
+    /// stdin, a FIFO, or a device; decoded as a byte stream.
+    ///
+    /// Boxed as `dyn Read + Send` because the decoder pipeline moves the reader to a dedicated
+    /// decode thread.
+    Stream(Box<dyn Read + Send>),
+}
+
+/// Open an input source.
 ///
 /// For stdin:
 /// - We use `io::stdin()` directly (not a lock guard).
 /// - This stays streaming-friendly and avoids temp files.
-fn open_input(path: &str) -> Result<Box<dyn Read + Send>> {
+fn open_input(path: &str) -> Result<Input> {
     if path == "-" {
-        Ok(Box::new(io::stdin()))
+        return Ok(Input::Stream(Box::new(io::stdin())));
+    }
+
+    let file = File::open(path).with_context(|| format!("failed to open input file: {path}"))?;
+    if is_regular_file(path) {
+        Ok(Input::File(file))
     } else {
-        let file =
-            File::open(path).with_context(|| format!("failed to open input file: {path}"))?;
-        Ok(Box::new(file))
+        Ok(Input::Stream(Box::new(file)))
     }
 }
 
@@ -77,14 +301,18 @@ fn open_input(path: &str) -> Result<Box<dyn Read + Send>> {
 #[derive(Parser, Debug)]
 #[command(name = "scribble")]
 #[command(about = "A transcription CLI (audio or video input)")]
+#[command(subcommand_negates_reqs = true, args_conflicts_with_subcommands = true)]
 struct Params {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// Path to a whisper.cpp model file (e.g. `ggml-large-v3.bin`).
     #[arg(short = 'm', long = "model", required = true)]
-    pub model_path: String,
+    pub model_path: Option<String>,
 
     /// Path to a Whisper-VAD model file.
     #[arg(short = 'v', long = "vad-model", required = true)]
-    pub vad_model_path: String,
+    pub vad_model_path: Option<String>,
 
     /// Input media path (audio or video), or "-" to read from stdin.
     ///
@@ -92,7 +320,18 @@ struct Params {
     ///   scribble -i samples/sintel_trailer-480p.mp4 ...
     ///   cat samples/audio.mp3 | scribble -i - ...
     #[arg(short = 'i', long = "input", required = true)]
-    pub input: String,
+    pub input: Option<String>,
+
+    /// Container format of the input, as a file extension (e.g. `mp4`, `mkv`, `ogg`).
+    ///
+    /// Defaults to the input file's extension; mostly useful with stdin.
+    #[arg(long = "format", value_name = "EXT", conflicts_with = "raw_format")]
+    pub format: Option<String>,
+
+    /// Read the input as headerless PCM: `ENCODING:SAMPLE_RATE:CHANNELS`, with ENCODING one of
+    /// `s16le`, `ulaw`, `alaw` (e.g. `s16le:8000:1`).
+    #[arg(long = "raw-format", value_name = "FORMAT")]
+    pub raw_format: Option<RawAudioFormat>,
 
     /// Output format for transcription segments.
     #[arg(
@@ -118,6 +357,126 @@ struct Params {
     /// Optional language hint (e.g. "en", "es").
     #[arg(short = 'l', long = "language")]
     pub language: Option<String>,
+
+    /// Emit one segment per word instead of one segment per phrase.
+    #[arg(long = "word-level-segments", default_value_t = false)]
+    pub enable_word_level_segments: bool,
+
+    /// Keep timestamp and control tokens (e.g. `[_TT_50]`) in each segment's `tokens` list.
+    #[arg(long = "keep-non-text-tokens", default_value_t = false)]
+    pub keep_non_text_tokens: bool,
+
+    /// Only emit streaming words confirmed by N consecutive passes (LocalAgreement-N).
+    ///
+    /// More stable output at the cost of latency. When omitted, all but the last segment of each
+    /// pass are emitted. Implies `--streaming`.
+    #[arg(
+        long = "local-agreement",
+        value_name = "N",
+        conflicts_with = "parallel"
+    )]
+    pub local_agreement: Option<usize>,
+
+    /// Maximum buffered audio (seconds) before streaming output is forced. Defaults to 30;
+    /// implies `--streaming`.
+    #[arg(long = "max-buffer-seconds", conflicts_with = "parallel")]
+    pub max_buffer_seconds: Option<usize>,
+
+    /// Emit provisional results while streaming (shown by `--output-type jsonl`). Implies
+    /// `--streaming`.
+    #[arg(
+        long = "partial-results",
+        default_value_t = false,
+        conflicts_with = "parallel"
+    )]
+    pub partial_results: bool,
+
+    /// Stream a regular file through the incremental transcriber instead of transcribing it in
+    /// one full-buffer pass (the default for files).
+    #[arg(
+        long = "streaming",
+        default_value_t = false,
+        conflicts_with = "parallel"
+    )]
+    pub streaming: bool,
+
+    /// Longest input (seconds) accepted for full-buffer transcription of a file.
+    #[arg(long = "max-full-seconds", default_value_t = 7200)]
+    pub max_full_seconds: usize,
+
+    /// Decode the whole input, split it at silences, and transcribe N chunks concurrently.
+    ///
+    /// Faster for long recordings on machines with many cores; output starts only once the
+    /// input has been fully decoded.
+    #[arg(
+        long = "parallel",
+        value_name = "N",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    pub parallel: Option<usize>,
+
+    /// Target chunk length (seconds) for `--parallel`.
+    #[arg(long = "chunk-seconds", default_value_t = 60)]
+    pub chunk_seconds: usize,
+
+    /// Audio track to transcribe in multi-track containers: an index (among audio tracks), a
+    /// language tag (e.g. `eng`), `first`, or `all` (mix every track into one stream; not for
+    /// tracks with simultaneous speech, such as dubbed languages).
+    #[arg(long = "track", value_name = "TRACK", default_value = "first")]
+    pub track: TrackSelection,
+
+    /// Transcribe each channel of the audio track separately (e.g. one speaker per side of a
+    /// stereo call recording) and label segments with their channel.
+    #[arg(long = "split-channels", default_value_t = false)]
+    pub split_channels: bool,
+
+    /// Start transcribing at this offset (seconds) into the input.
+    ///
+    /// Timestamps stay relative to the start of the input.
+    #[arg(long = "start", value_name = "SECONDS")]
+    pub start_seconds: Option<f32>,
+
+    /// Stop transcribing at this offset (seconds) into the input.
+    #[arg(long = "end", value_name = "SECONDS")]
+    pub end_seconds: Option<f32>,
+
+    /// Only transcribe the first N seconds of input audio.
+    #[arg(long = "max-audio-seconds", value_name = "N")]
+    pub max_audio_seconds: Option<usize>,
+
+    /// Stop reading input after N seconds of wall-clock time; audio already read is still
+    /// transcribed.
+    #[arg(long = "max-wall-clock-seconds", value_name = "N")]
+    pub max_wall_clock_seconds: Option<usize>,
+
+    /// Drop segments that look hallucinated (phantom text over silence, repetition loops).
+    ///
+    /// Counts of dropped segments are logged at `info` level.
+    #[arg(long = "filter-hallucinations", default_value_t = false)]
+    pub filter_hallucinations: bool,
+
+    /// Extra phrase to treat as a hallucination (repeatable); requires `--filter-hallucinations`.
+    #[arg(
+        long = "hallucination-phrase",
+        value_name = "TEXT",
+        requires = "filter_hallucinations"
+    )]
+    pub hallucination_phrases: Vec<String>,
+
+    /// Disable the progress bar on stderr.
+    #[arg(long = "no-progress", default_value_t = false)]
+    pub no_progress: bool,
+}
+
+/// Subcommands; without one, `scribble` transcribes `--input`.
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Print container and audio track metadata (duration, codecs, channels, tags) as JSON
+    /// without transcribing.
+    Probe {
+        /// Input media path, or "-" to read from stdin.
+        input: String,
+    },
 }
 
 #[cfg(test)]
@@ -130,11 +489,26 @@ mod tests {
             Params::try_parse_from(["scribble", "-m", "model.bin", "-v", "vad.bin", "-i", "-"])
                 .expect("parse params");
 
-        assert_eq!(params.input, "-");
+        assert_eq!(params.input.as_deref(), Some("-"));
         assert!(matches!(params.output_type, OutputType::Vtt));
         assert!(!params.enable_voice_activity_detection);
         assert!(!params.enable_translation_to_english);
         assert!(params.language.is_none());
+        assert!(!params.enable_word_level_segments);
+        assert!(!params.keep_non_text_tokens);
+        assert!(!params.no_progress);
+        assert!(params.local_agreement.is_none());
+        assert!(params.max_buffer_seconds.is_none());
+        assert!(!params.partial_results);
+        assert!(hallucination_filter(&params).is_none());
+        assert_eq!(
+            transcription_mode(&params, false),
+            TranscriptionMode::Streaming
+        );
+        assert_eq!(
+            commit_policy(params.local_agreement),
+            CommitPolicy::AllButLast
+        );
     }
 
     #[test]
@@ -153,6 +527,12 @@ mod tests {
             "-t",
             "-l",
             "en",
+            "--word-level-segments",
+            "--keep-non-text-tokens",
+            "--local-agreement",
+            "2",
+            "--max-buffer-seconds",
+            "12",
         ])
         .expect("parse params");
 
@@ -160,6 +540,195 @@ mod tests {
         assert!(params.enable_voice_activity_detection);
         assert!(params.enable_translation_to_english);
         assert_eq!(params.language.as_deref(), Some("en"));
+        assert!(params.enable_word_level_segments);
+        assert!(params.keep_non_text_tokens);
+        assert_eq!(params.max_buffer_seconds, Some(12));
+        assert_eq!(
+            commit_policy(params.local_agreement),
+            CommitPolicy::LocalAgreement { n: 2 }
+        );
+    }
+
+    #[test]
+    fn params_parses_jsonl_with_partial_results() {
+        let params = Params::try_parse_from([
+            "scribble",
+            "-m",
+            "model.bin",
+            "-v",
+            "vad.bin",
+            "-i",
+            "-",
+            "-o",
+            "jsonl",
+            "--partial-results",
+        ])
+        .expect("parse params");
+
+        assert!(matches!(params.output_type, OutputType::JsonLines));
+        assert!(params.partial_results);
+    }
+
+    #[test]
+    fn params_parses_parallel_chunks() {
+        let params = Params::try_parse_from([
+            "scribble",
+            "-m",
+            "model.bin",
+            "-v",
+            "vad.bin",
+            "-i",
+            "podcast.mp3",
+            "--parallel",
+            "8",
+            "--chunk-seconds",
+            "120",
+        ])
+        .expect("parse params");
+
+        assert_eq!(
+            transcription_mode(&params, true),
+            TranscriptionMode::ParallelChunks {
+                workers: 8,
+                chunk_seconds: 120
+            }
+        );
+    }
+
+    #[test]
+    fn regular_files_default_to_full_buffer_mode() {
+        let params = Params::try_parse_from([
+            "scribble",
+            "-m",
+            "model.bin",
+            "-v",
+            "vad.bin",
+            "-i",
+            "tests/fixtures/jfk.wav",
+        ])
+        .expect("parse params");
+
+        assert!(params.input.as_deref().is_some_and(is_regular_file));
+        assert_eq!(
+            transcription_mode(&params, true),
+            TranscriptionMode::Full { max_seconds: 7200 }
+        );
+    }
+
+    #[test]
+    fn streaming_flag_overrides_full_buffer_default() {
+        let params = Params::try_parse_from([
+            "scribble",
+            "-m",
+            "model.bin",
+            "-v",
+            "vad.bin",
+            "-i",
+            "tests/fixtures/jfk.wav",
+            "--streaming",
+        ])
+        .expect("parse params");
+
+        assert_eq!(
+            transcription_mode(&params, true),
+            TranscriptionMode::Streaming
+        );
+    }
+
+    #[test]
+    fn incremental_flags_imply_streaming_for_files() {
+        for flags in [
+            &["--local-agreement", "2"][..],
+            &["--partial-results"],
+            &["--max-buffer-seconds", "20"],
+        ] {
+            let mut args = vec![
+                "scribble",
+                "-m",
+                "model.bin",
+                "-v",
+                "vad.bin",
+                "-i",
+                "tests/fixtures/jfk.wav",
+            ];
+            args.extend_from_slice(flags);
+            let params = Params::try_parse_from(args).expect("parse params");
+
+            assert_eq!(
+                transcription_mode(&params, true),
+                TranscriptionMode::Streaming,
+                "{flags:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn incremental_flags_conflict_with_parallel() {
+        let err = Params::try_parse_from([
+            "scribble",
+            "-m",
+            "model.bin",
+            "-v",
+            "vad.bin",
+            "-i",
+            "podcast.mp3",
+            "--parallel",
+            "4",
+            "--partial-results",
+        ])
+        .unwrap_err();
+
+        assert_eq!(err.kind(), clap::error::ErrorKind::ArgumentConflict);
+    }
+
+    #[test]
+    fn params_builds_hallucination_filter_with_extra_phrases() {
+        let params = Params::try_parse_from([
+            "scribble",
+            "-m",
+            "model.bin",
+            "-v",
+            "vad.bin",
+            "-i",
+            "-",
+            "--filter-hallucinations",
+            "--hallucination-phrase",
+            "see you next time",
+        ])
+        .expect("parse params");
+
+        let filter = hallucination_filter(&params).expect("filter enabled");
+        assert!(filter.phrases.iter().any(|p| p == "see you next time"));
+        assert!(filter.phrases.len() > 1);
+    }
+
+    #[test]
+    fn stdin_is_not_a_regular_file() {
+        assert!(!is_regular_file("-"));
+        assert!(!is_regular_file("definitely-not-a-real-file"));
+    }
+
+    #[test]
+    fn params_rejects_zero_parallel_workers() {
+        let res = Params::try_parse_from([
+            "scribble",
+            "-m",
+            "model.bin",
+            "-v",
+            "vad.bin",
+            "-i",
+            "-",
+            "--parallel",
+            "0",
+        ]);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn format_clock_renders_hours_minutes_seconds() {
+        assert_eq!(format_clock(0.0), "00:00:00");
+        assert_eq!(format_clock(3725.9), "01:02:05");
+        assert_eq!(format_clock(-1.0), "00:00:00");
     }
 
     #[test]
@@ -169,4 +738,72 @@ mod tests {
             .expect("expected open_input() to error");
         assert!(err.to_string().contains("failed to open input file"));
     }
+
+    #[test]
+    fn open_input_opens_regular_files_as_seekable() -> anyhow::Result<()> {
+        assert!(matches!(
+            open_input("tests/fixtures/jfk.wav")?,
+            Input::File(_)
+        ));
+        assert!(matches!(open_input("-")?, Input::Stream(_)));
+        Ok(())
+    }
+
+    #[test]
+    fn probe_subcommand_needs_no_models() {
+        let params = Params::try_parse_from(["scribble", "probe", "tests/fixtures/jfk.wav"])
+            .expect("parse params");
+
+        assert!(matches!(
+            params.command,
+            Some(Command::Probe { ref input }) if input == "tests/fixtures/jfk.wav"
+        ));
+        assert!(Params::try_parse_from(["scribble", "-i", "-"]).is_err());
+    }
+
+    #[test]
+    fn input_format_comes_from_flag_or_extension() {
+        assert_eq!(input_format(None, "talk.MP4").as_deref(), Some("mp4"));
+        assert_eq!(input_format(Some(".mkv"), "-").as_deref(), Some("mkv"));
+        assert_eq!(input_format(None, "-"), None);
+        assert_eq!(input_format(None, "recording"), None);
+    }
+
+    #[test]
+    fn params_parses_raw_format() {
+        let params = Params::try_parse_from([
+            "scribble",
+            "-m",
+            "model.bin",
+            "-v",
+            "vad.bin",
+            "-i",
+            "-",
+            "--raw-format",
+            "alaw:8000:1",
+        ])
+        .expect("parse params");
+
+        assert_eq!(
+            params
+                .raw_format
+                .map(|format| format.to_string())
+                .as_deref(),
+            Some("alaw:8000:1")
+        );
+        assert!(
+            Params::try_parse_from([
+                "scribble",
+                "-m",
+                "model.bin",
+                "-v",
+                "vad.bin",
+                "-i",
+                "-",
+                "--raw-format",
+                "s16le",
+            ])
+            .is_err()
+        );
+    }
 }
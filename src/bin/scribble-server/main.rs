@@ -1,7 +1,7 @@
 use anyhow::{Context, Result, anyhow};
 use axum::body::{Body, Bytes};
 use axum::extract::{DefaultBodyLimit, Query, State};
-use axum::http::{HeaderValue, StatusCode, header};
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
 use axum::middleware::from_fn;
 use axum::response::{IntoResponse, Response};
 use axum::routing::{get, post};
@@ -10,25 +10,44 @@ use clap::Parser;
 use futures_util::stream::BoxStream;
 use futures_util::{StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
-use std::io::Cursor;
+use std::collections::BTreeMap;
+use std::io::{Cursor, Seek};
 use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Duration;
-use symphonia::core::io::ReadOnlySource;
+use tokio::io::{AsyncRead, AsyncWriteExt};
 use tokio::net::TcpListener;
 use tokio::signal;
-use tokio::sync::oneshot;
-use tokio_util::io::{ReaderStream, SyncIoBridge};
+use tokio_util::io::ReaderStream;
 use tower_http::timeout::TimeoutLayer;
 use tower_http::trace::{DefaultMakeSpan, DefaultOnFailure, DefaultOnResponse, TraceLayer};
 use tracing::{Level, error, info, warn};
 
 mod metrics;
 
-use scribble::{Opts, OutputType, Scribble, WhisperBackend};
+use scribble::{
+    Backend, CancellationToken, Capabilities, CommitPolicy, FilterCounts, HallucinationFilter,
+    MediaInfo, Opts, OutputType, RawAudioFormat, Scribble, TrackSelection, TranscriptionMode,
+    WhisperBackend,
+};
 
 type BodyDataStream = BoxStream<'static, std::result::Result<Bytes, axum::Error>>;
 
+/// How long a request may take to get a response started.
+///
+/// `/transcribe` applies it to reading and probing the start of the upload only: spooling a
+/// complete upload can take longer, and is bounded by `--max-bytes` instead.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Largest accepted `max_wall_clock_seconds` (one day).
+const MAX_WALL_CLOCK_SECONDS: usize = 24 * 60 * 60;
+
+/// Largest accepted `max_buffer_seconds`; bounds the audio buffered per request.
+const MAX_BUFFER_SECONDS: usize = 120;
+
+/// Largest accepted `local_agreement`; larger values commit nothing until the buffer is full.
+const MAX_LOCAL_AGREEMENT: usize = 10;
+
 #[derive(Parser, Debug)]
 #[command(name = "scribble-server")]
 #[command(about = "HTTP server for audio/video transcription")]
@@ -57,6 +76,8 @@ struct Params {
 #[derive(Clone)]
 struct AppState {
     scribble: Arc<Scribble<WhisperBackend>>,
+    /// Upload size limit (`--max-bytes`), also applied when spooling an upload to disk.
+    max_bytes: usize,
 }
 
 #[derive(Debug, Deserialize)]
@@ -71,6 +92,32 @@ struct TranscribeQuery {
     translate_to_english: Option<bool>,
     #[serde(default)]
     language: Option<String>,
+    #[serde(default)]
+    word_level_segments: Option<bool>,
+    #[serde(default)]
+    non_text_tokens: Option<bool>,
+    #[serde(default)]
+    local_agreement: Option<usize>,
+    #[serde(default)]
+    max_buffer_seconds: Option<usize>,
+    #[serde(default)]
+    partial_results: Option<bool>,
+    #[serde(default)]
+    filter_hallucinations: Option<bool>,
+    #[serde(default)]
+    track: Option<String>,
+    #[serde(default)]
+    raw_format: Option<String>,
+    #[serde(default)]
+    split_channels: Option<bool>,
+    #[serde(default)]
+    start_seconds: Option<f32>,
+    #[serde(default)]
+    end_seconds: Option<f32>,
+    #[serde(default)]
+    max_audio_seconds: Option<usize>,
+    #[serde(default)]
+    max_wall_clock_seconds: Option<usize>,
 }
 
 #[derive(Debug, Serialize)]
@@ -78,6 +125,8 @@ struct ModelsResponse {
     default_model_key: String,
     model_keys: Vec<String>,
     vad_model_path: String,
+    /// Per model key.
+    capabilities: BTreeMap<String, Capabilities>,
 }
 
 #[derive(Debug, Serialize)]
@@ -104,6 +153,27 @@ impl AppError {
             message: message.into(),
         }
     }
+
+    fn payload_too_large(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::PAYLOAD_TOO_LARGE,
+            message: message.into(),
+        }
+    }
+
+    fn request_timeout(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::REQUEST_TIMEOUT,
+            message: message.into(),
+        }
+    }
+
+    fn internal(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::INTERNAL_SERVER_ERROR,
+            message: message.into(),
+        }
+    }
 }
 
 impl IntoResponse for AppError {
@@ -141,6 +211,7 @@ async fn run() -> Result<()> {
 
     let state = AppState {
         scribble: Arc::new(scribble),
+        max_bytes: params.max_bytes,
     };
 
     let app = Router::new()
@@ -148,6 +219,11 @@ async fn run() -> Result<()> {
         .route("/health", get(health))
         .route("/metrics", get(metrics::prometheus_metrics))
         .route("/models", get(models))
+        // Only covers the routes above; `/transcribe` times out its upload probe itself.
+        .layer(TimeoutLayer::with_status_code(
+            StatusCode::REQUEST_TIMEOUT,
+            REQUEST_TIMEOUT,
+        ))
         .route("/transcribe", post(transcribe))
         .route_layer(from_fn(metrics::track_http_metrics))
         .with_state(state)
@@ -161,11 +237,7 @@ async fn run() -> Result<()> {
                 )
                 .on_response(DefaultOnResponse::new().level(Level::INFO))
                 .on_failure(DefaultOnFailure::new().level(Level::ERROR)),
-        )
-        .layer(TimeoutLayer::with_status_code(
-            StatusCode::REQUEST_TIMEOUT,
-            Duration::from_secs(10),
-        ));
+        );
 
     let listener = TcpListener::bind(addr).await.context("bind failed")?;
 
@@ -191,32 +263,100 @@ async fn models(
     State(state): State<AppState>,
 ) -> std::result::Result<Json<ModelsResponse>, AppError> {
     let backend = state.scribble.backend();
+    let model_keys = backend.model_keys();
+    let capabilities = model_keys
+        .iter()
+        .map(|key| (key.clone(), backend.capabilities(Some(key))))
+        .collect();
 
     Ok(Json(ModelsResponse {
         default_model_key: backend.default_model_key().to_owned(),
-        model_keys: backend.model_keys(),
+        model_keys,
         vad_model_path: backend.vad_model_path().to_owned(),
+        capabilities,
     }))
 }
 
 async fn transcribe(
     State(state): State<AppState>,
     Query(query): Query<TranscribeQuery>,
+    headers: HeaderMap,
     body: Body,
 ) -> std::result::Result<Response, AppError> {
-    // We want request bodies to be streaming (for very long/live uploads), but we still want to
-    // fail fast for obviously unsupported inputs. We do a small, bounded probe against the
-    // initial prefix and then replay that prefix into the decoder so transcription starts at
-    // byte 0 without buffering the whole upload.
-    const MAX_PROBE_BYTES: usize = 512 * 1024;
-    let body_stream: BodyDataStream = body.into_data_stream().boxed();
-    let (prefix_bytes, prefix_chunks, body_stream) =
-        get_prefix_bytes(body_stream, MAX_PROBE_BYTES).await?;
+    check_at_most(
+        "max_wall_clock_seconds",
+        query.max_wall_clock_seconds,
+        MAX_WALL_CLOCK_SECONDS,
+    )?;
+    check_at_most(
+        "max_buffer_seconds",
+        query.max_buffer_seconds,
+        MAX_BUFFER_SECONDS,
+    )?;
+    check_at_most(
+        "local_agreement",
+        query.local_agreement,
+        MAX_LOCAL_AGREEMENT,
+    )?;
+
+    let raw_input_format = query
+        .raw_format
+        .as_deref()
+        .map(str::parse::<RawAudioFormat>)
+        .transpose()
+        .map_err(|err| AppError::bad_request(err.to_string()))?;
+
+    let audio_track = query
+        .track
+        .as_deref()
+        .map(str::parse::<TrackSelection>)
+        .transpose()
+        .map_err(|err| AppError::bad_request(err.to_string()))?
+        .unwrap_or_default();
+
+    let input_format = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(format_hint_for_content_type);
 
-    validate_media_prefix(&prefix_bytes)?;
+    // Per-channel transcription decodes the upload once per channel, so it needs a seekable copy.
+    // Spooling it here keeps that copy under `--max-bytes`.
+    let replayable = query.split_channels.unwrap_or(false);
+
+    let body_stream: BodyDataStream = body.into_data_stream().boxed();
+    let input = if raw_input_format.is_some() {
+        // Headerless PCM has no container to probe; hand the body straight to the decoder.
+        if replayable {
+            UploadInput::Spooled(spool_to_tempfile(Vec::new(), body_stream, state.max_bytes).await?)
+        } else {
+            let reader =
+                tokio_util::io::StreamReader::new(body_stream.map_err(std::io::Error::other));
+            UploadInput::Stream(Box::new(reader))
+        }
+    } else {
+        accept_media_upload(
+            body_stream,
+            input_format,
+            &audio_track,
+            state.max_bytes,
+            replayable,
+        )
+        .await?
+    };
 
     let output_type = parse_output_type(query.output.as_deref())
         .map_err(|err| AppError::bad_request(err.to_string()))?;
+    // Cancelled when the response body is dropped (client disconnect), so whisper stops instead of
+    // transcribing the rest of the upload for nobody.
+    let cancellation_token = CancellationToken::new();
+
+    let hallucination_filter = query
+        .filter_hallucinations
+        .unwrap_or(false)
+        .then(HallucinationFilter::default);
+    let filter_stats = hallucination_filter
+        .as_ref()
+        .map(|filter| filter.stats.clone());
 
     let opts = Opts {
         model_key: query.model_key,
@@ -224,49 +364,153 @@ async fn transcribe(
         enable_voice_activity_detection: query.enable_vad.unwrap_or(false),
         language: query.language,
         output_type,
+        transcription_mode: TranscriptionMode::Streaming,
         incremental_min_window_seconds: 1,
+        incremental_max_buffer_seconds: query.max_buffer_seconds.unwrap_or(30),
+        incremental_commit_policy: match query.local_agreement {
+            Some(n) => CommitPolicy::LocalAgreement { n },
+            None => CommitPolicy::AllButLast,
+        },
+        enable_partial_results: query.partial_results.unwrap_or(false),
+        enable_word_level_segments: query.word_level_segments.unwrap_or(false),
+        enable_non_text_tokens: query.non_text_tokens.unwrap_or(false),
+        input_format: input_format.map(str::to_owned),
+        raw_input_format,
+        audio_track,
+        enable_per_channel_transcription: query.split_channels.unwrap_or(false),
+        start_seconds: query.start_seconds,
+        end_seconds: query.end_seconds,
+        max_audio_seconds: query.max_audio_seconds,
+        max_wall_clock_seconds: query.max_wall_clock_seconds,
+        hallucination_filter,
+        cancellation_token: Some(cancellation_token.clone()),
+        progress_listener: None,
     };
 
+    // Reject unsupported options while an error status can still be returned.
+    state
+        .scribble
+        .validate_opts(&opts)
+        .map_err(|err| AppError::bad_request(err.to_string()))?;
+
     let content_type = match opts.output_type {
         OutputType::Json => HeaderValue::from_static("application/json; charset=utf-8"),
         OutputType::Vtt => HeaderValue::from_static("text/vtt; charset=utf-8"),
+        OutputType::JsonLines => HeaderValue::from_static("application/x-ndjson; charset=utf-8"),
     };
 
     let scribble = state.scribble.clone();
-    let prefix_stream =
-        futures_util::stream::iter(prefix_chunks.into_iter().map(Ok::<Bytes, axum::Error>));
-    let input_stream = prefix_stream.chain(body_stream);
-    let input_reader =
-        tokio_util::io::StreamReader::new(input_stream.map_err(std::io::Error::other));
-
     let (out_tx, out_rx) = tokio::io::duplex(64 * 1024);
-    let (done_tx, done_rx) = oneshot::channel::<std::result::Result<(), String>>();
-
-    tokio::task::spawn_blocking(move || {
-        let mut writer = SyncIoBridge::new(out_tx);
-        let input = SyncIoBridge::new(input_reader);
-        let res = scribble
-            .transcribe(input, &mut writer, &opts)
-            .map_err(|err| err.to_string());
-        let _ = done_tx.send(res);
-    });
+    // JSON Lines responses end with the filter counts once transcription succeeds.
+    let (summary_tx, summary_rx) = tokio::sync::oneshot::channel::<Bytes>();
+    let send_summary = matches!(opts.output_type, OutputType::JsonLines);
 
     tokio::spawn(async move {
-        if let Ok(Err(msg)) = done_rx.await {
-            error!(%msg, "transcription failed");
+        let res = match input {
+            UploadInput::Stream(reader) => scribble.transcribe_async(reader, out_tx, &opts).await,
+            UploadInput::Spooled(file) => {
+                scribble
+                    .transcribe_seekable_async(file, out_tx, &opts)
+                    .await
+            }
+        };
+        let succeeded = res.is_ok();
+        match res {
+            Err(scribble::Error::Cancelled) => info!("transcription cancelled"),
+            Err(err) => error!(msg = %err, "transcription failed"),
+            Ok(()) => {}
+        }
+        if let Some(counts) = filter_stats.map(|stats| stats.counts()) {
+            if counts.total() > 0 {
+                info!(
+                    no_speech = counts.no_speech,
+                    low_probability = counts.low_probability,
+                    repetition = counts.repetition,
+                    phrase = counts.phrase,
+                    "filtered hallucinated segments"
+                );
+            }
+            if send_summary && succeeded {
+                let _ = summary_tx.send(filter_summary_line(counts));
+            }
         }
     });
 
-    let body = Body::from_stream(ReaderStream::new(out_rx));
+    let cancel_guard = CancelOnDrop(cancellation_token);
+    // A dropped sender (no filter, other output types, failed transcription) ends the body as is.
+    let summary =
+        futures_util::stream::once(summary_rx).filter_map(|line| async move { line.ok().map(Ok) });
+    let out_stream = ReaderStream::new(out_rx).chain(summary).map(move |chunk| {
+        // The guard lives as long as the body stream; see `CancelOnDrop`.
+        let _ = &cancel_guard;
+        chunk
+    });
+    let body = Body::from_stream(out_stream);
     Ok(([(header::CONTENT_TYPE, content_type)], body).into_response())
 }
 
+/// Trailing JSON Lines record with the number of segments dropped by the hallucination filter.
+fn filter_summary_line(counts: FilterCounts) -> Bytes {
+    let line = serde_json::json!({
+        "status": "summary",
+        "filtered": {
+            "no_speech": counts.no_speech,
+            "low_probability": counts.low_probability,
+            "repetition": counts.repetition,
+            "phrase": counts.phrase,
+        },
+    });
+    Bytes::from(format!("{line}\n"))
+}
+
+/// Request body handed to the transcriber.
+enum UploadInput {
+    /// Streamed straight into the decoder (the common case).
+    Stream(Box<dyn AsyncRead + Unpin + Send>),
+
+    /// Spooled to an (already unlinked) temp file so the decoder can seek.
+    Spooled(std::fs::File),
+}
+
+/// Reject a numeric query param above `max` while an error status can still be returned.
+fn check_at_most(
+    name: &str,
+    value: Option<usize>,
+    max: usize,
+) -> std::result::Result<(), AppError> {
+    match value {
+        Some(value) if value > max => Err(AppError::bad_request(format!(
+            "{name} must be at most {max} (got {value})"
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Cancels the associated transcription when dropped.
+///
+/// Owned by the response body stream. Hyper drops the body when the client disconnects, which
+/// aborts the blocking transcription. On normal completion the transcription has already
+/// finished, so the cancel is a no-op.
+struct CancelOnDrop(CancellationToken);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.cancel();
+    }
+}
+
 async fn get_prefix_bytes(
     mut body_stream: BodyDataStream,
+    hint: Option<&str>,
     max_probe_bytes: usize,
 ) -> std::result::Result<(Vec<u8>, Vec<Bytes>, BodyDataStream), AppError> {
+    // Bytes buffered before the first early probe; each later probe waits for twice as many, so
+    // a body arriving in many small chunks is probed only a few times.
+    const FIRST_PROBE_BYTES: usize = 4 * 1024;
+
     let mut prefix_bytes = Vec::<u8>::new();
     let mut prefix_chunks = Vec::<Bytes>::new();
+    let mut next_probe_at = FIRST_PROBE_BYTES;
 
     while prefix_bytes.len() < max_probe_bytes {
         let Some(chunk) = body_stream.next().await else {
@@ -281,6 +525,15 @@ async fn get_prefix_bytes(
         if chunk.len() <= remaining {
             prefix_bytes.extend_from_slice(&chunk);
             prefix_chunks.push(chunk);
+
+            // Stop as soon as the container is recognized: live uploads (e.g. from a remote
+            // backend) may take longer than the request timeout to fill the whole probe window.
+            if prefix_bytes.len() >= next_probe_at {
+                next_probe_at = prefix_bytes.len().saturating_mul(2);
+                if validate_media_prefix(&prefix_bytes, hint).await.is_ok() {
+                    break;
+                }
+            }
             continue;
         }
 
@@ -303,57 +556,180 @@ async fn get_prefix_bytes(
     Ok((prefix_bytes, prefix_chunks, body_stream))
 }
 
-fn validate_media_prefix(prefix: &[u8]) -> std::result::Result<(), AppError> {
-    let source = ReadOnlySource::new(Cursor::new(prefix.to_vec()));
-    if let Err(err) = probe_source_and_pick_default_track(Box::new(source), None) {
-        return Err(AppError::unsupported_media(format!(
-            "unsupported or unrecognized media container: {err}"
-        )));
-    }
-    Ok(())
-}
+/// Probe a container upload, spooling it to a temp file when it is an MP4/MOV whose start cannot
+/// be probed, or when `replayable` asks for a seekable copy. `hint` is the container hint derived
+/// from the request's `Content-Type`.
+///
+/// Uploads without a track matching `track` are rejected with a list of the available tracks.
+async fn accept_media_upload(
+    body_stream: BodyDataStream,
+    hint: Option<&str>,
+    track: &TrackSelection,
+    max_bytes: usize,
+    replayable: bool,
+) -> std::result::Result<UploadInput, AppError> {
+    // We want request bodies to be streaming (for very long/live uploads), but we still want to
+    // fail fast for obviously unsupported inputs. We do a small, bounded probe against the
+    // initial prefix and then replay that prefix into the decoder so transcription starts at
+    // byte 0 without buffering the whole upload.
+    const MAX_PROBE_BYTES: usize = 512 * 1024;
+    let (prefix_bytes, prefix_chunks, body_stream) = tokio::time::timeout(
+        REQUEST_TIMEOUT,
+        get_prefix_bytes(body_stream, hint, MAX_PROBE_BYTES),
+    )
+    .await
+    .map_err(|_| AppError::request_timeout("timed out waiting for the start of the upload"))??;
+
+    let check_track = |media: &MediaInfo| {
+        media
+            .select_tracks(track)
+            .map(|_| ())
+            .map_err(|err| AppError::bad_request(err.to_string()))
+    };
 
-fn probe_source_and_pick_default_track(
-    source: Box<dyn symphonia::core::io::MediaSource>,
-    hint_extension: Option<&str>,
-) -> Result<(
-    Box<dyn symphonia::core::formats::FormatReader>,
-    symphonia::core::formats::Track,
-)> {
-    use symphonia::core::codecs::CODEC_TYPE_NULL;
-    use symphonia::core::io::{MediaSourceStream, MediaSourceStreamOptions};
-    use symphonia::core::meta::MetadataOptions;
-    use symphonia::core::probe::Hint;
-
-    let mss_opts = MediaSourceStreamOptions {
-        buffer_len: 256 * 1024,
+    let (input, media) = match validate_media_prefix(&prefix_bytes, hint).await {
+        Ok(media) if replayable => {
+            check_track(&media)?;
+            let file = spool_to_tempfile(prefix_chunks, body_stream, max_bytes).await?;
+            (UploadInput::Spooled(file), media)
+        }
+        Ok(media) => {
+            check_track(&media)?;
+            let prefix_stream =
+                futures_util::stream::iter(prefix_chunks.into_iter().map(Ok::<Bytes, axum::Error>));
+            let input_stream = prefix_stream.chain(body_stream);
+            let reader =
+                tokio_util::io::StreamReader::new(input_stream.map_err(std::io::Error::other));
+            (UploadInput::Stream(Box::new(reader)), media)
+        }
+        // MP4/MOV files with a trailing `moov` box can only be probed with seeking. Spool those
+        // (up to the upload limit) to a temp file and probe the complete upload instead; reject
+        // anything else right away rather than writing it to disk.
+        Err(_) if is_iso_bmff(&prefix_bytes) => {
+            let file = spool_to_tempfile(prefix_chunks, body_stream, max_bytes).await?;
+            let media = validate_media_file(&file, hint).await?;
+            check_track(&media)?;
+            (UploadInput::Spooled(file), media)
+        }
+        Err(err) => return Err(err),
     };
+    info!(
+        duration_seconds = ?media.duration_seconds,
+        audio_tracks = media.tracks.len(),
+        "accepted upload"
+    );
+    Ok(input)
+}
 
-    let mss = MediaSourceStream::new(source, mss_opts);
+/// Probe the start of an upload on the blocking pool.
+async fn validate_media_prefix(
+    prefix: &[u8],
+    hint: Option<&str>,
+) -> std::result::Result<MediaInfo, AppError> {
+    let prefix = prefix.to_vec();
+    let hint = hint.map(str::to_owned);
+    let probed =
+        tokio::task::spawn_blocking(move || scribble::probe(Cursor::new(prefix), hint.as_deref()))
+            .await
+            .map_err(|err| AppError::internal(format!("upload probe failed: {err}")))?;
+    check_probed_media(probed)
+}
 
-    let mut hint = Hint::new();
-    if let Some(ext) = hint_extension {
-        hint.with_extension(ext);
-    }
+/// Like `validate_media_prefix`, but probes a complete, seekable upload.
+async fn validate_media_file(
+    file: &std::fs::File,
+    hint: Option<&str>,
+) -> std::result::Result<MediaInfo, AppError> {
+    let clone = file
+        .try_clone()
+        .map_err(|err| AppError::internal(format!("failed to reopen spooled upload: {err}")))?;
+    let hint = hint.map(str::to_owned);
+    let probed =
+        tokio::task::spawn_blocking(move || scribble::probe_seekable(clone, hint.as_deref()))
+            .await
+            .map_err(|err| AppError::internal(format!("upload probe failed: {err}")))?;
+    let info = check_probed_media(probed)?;
+
+    // The clone shares the file offset with `file`; rewind for the decoder.
+    let mut file = file;
+    file.rewind()
+        .map_err(|err| AppError::internal(format!("failed to rewind spooled upload: {err}")))?;
+    Ok(info)
+}
 
-    let format_opts: symphonia::core::formats::FormatOptions = Default::default();
-    let metadata_opts: MetadataOptions = Default::default();
+/// Reject uploads whose container is unrecognized or holds no decodable audio.
+fn check_probed_media(
+    probed: scribble::Result<MediaInfo>,
+) -> std::result::Result<MediaInfo, AppError> {
+    let info = probed.map_err(|err| {
+        AppError::unsupported_media(format!(
+            "unsupported or unrecognized media container: {err}"
+        ))
+    })?;
+    if info.tracks.is_empty() {
+        return Err(AppError::unsupported_media(
+            "the media container has no audio track that can be decoded",
+        ));
+    }
+    Ok(info)
+}
 
-    let probed = symphonia::default::get_probe()
-        .format(&hint, mss, &format_opts, &metadata_opts)
-        .map_err(|e| anyhow!(e))
-        .context("failed to probe media stream")?;
+/// Whether `prefix` starts like an ISO-BMFF file (MP4, MOV, M4A, ...): a box of type `ftyp`.
+fn is_iso_bmff(prefix: &[u8]) -> bool {
+    prefix.get(4..8) == Some(b"ftyp")
+}
 
-    let format = probed.format;
+/// Write the whole upload (replaying the already-read prefix) to an anonymous temp file.
+///
+/// Fails with `413 Payload Too Large` once more than `max_bytes` have been written.
+async fn spool_to_tempfile(
+    prefix_chunks: Vec<Bytes>,
+    body_stream: BodyDataStream,
+    max_bytes: usize,
+) -> std::result::Result<std::fs::File, AppError> {
+    let spool_err =
+        |err: std::io::Error| AppError::internal(format!("failed to spool upload: {err}"));
+
+    let mut file = tokio::fs::File::from_std(tempfile::tempfile().map_err(spool_err)?);
+    let mut chunks =
+        futures_util::stream::iter(prefix_chunks.into_iter().map(Ok)).chain(body_stream);
+    let mut written = 0usize;
+    while let Some(chunk) = chunks.next().await {
+        let chunk = chunk.map_err(|err| AppError::bad_request(err.to_string()))?;
+        written = written.saturating_add(chunk.len());
+        if written > max_bytes {
+            return Err(AppError::payload_too_large(format!(
+                "upload exceeds the {max_bytes}-byte limit"
+            )));
+        }
+        file.write_all(&chunk).await.map_err(spool_err)?;
+    }
+    file.flush().await.map_err(spool_err)?;
 
-    let track = format
-        .tracks()
-        .iter()
-        .find(|t| t.codec_params.codec != CODEC_TYPE_NULL && t.codec_params.sample_rate.is_some())
-        .cloned()
-        .ok_or_else(|| anyhow!("no audio track found"))?;
+    let mut file = file.into_std().await;
+    file.rewind().map_err(spool_err)?;
+    Ok(file)
+}
 
-    Ok((format, track))
+/// Map an upload's `Content-Type` to a container hint (file extension) for the decoder.
+///
+/// Generic types such as `application/octet-stream` give no hint.
+fn format_hint_for_content_type(content_type: &str) -> Option<&'static str> {
+    let mime = content_type.split(';').next()?.trim().to_ascii_lowercase();
+    Some(match mime.as_str() {
+        "audio/wav" | "audio/wave" | "audio/x-wav" | "audio/vnd.wave" => "wav",
+        "audio/mpeg" | "audio/mp3" => "mp3",
+        "audio/mp4" | "audio/m4a" | "audio/x-m4a" | "video/mp4" => "mp4",
+        "video/quicktime" => "mov",
+        "audio/ogg" | "audio/opus" | "audio/vorbis" | "application/ogg" | "video/ogg" => "ogg",
+        "audio/webm" | "video/webm" => "webm",
+        "audio/x-matroska" | "video/x-matroska" => "mkv",
+        "audio/flac" | "audio/x-flac" => "flac",
+        "audio/aac" | "audio/aacp" => "aac",
+        "audio/aiff" | "audio/x-aiff" => "aiff",
+        "audio/x-caf" => "caf",
+        _ => return None,
+    })
 }
 
 fn parse_output_type(output: Option<&str>) -> Result<OutputType> {
@@ -362,8 +738,9 @@ fn parse_output_type(output: Option<&str>) -> Result<OutputType> {
         Some(raw) => match raw.trim().to_ascii_lowercase().as_str() {
             "json" => Ok(OutputType::Json),
             "vtt" => Ok(OutputType::Vtt),
+            "jsonl" | "ndjson" => Ok(OutputType::JsonLines),
             other => Err(anyhow!(
-                "unknown output type '{other}' (expected 'json' or 'vtt')"
+                "unknown output type '{other}' (expected 'json', 'jsonl', or 'vtt')"
             )),
         },
     }
@@ -420,6 +797,43 @@ mod tests {
             OutputType::Json
         ));
         assert!(matches!(parse_output_type(Some("VTT"))?, OutputType::Vtt));
+        assert!(matches!(
+            parse_output_type(Some("jsonl"))?,
+            OutputType::JsonLines
+        ));
+        assert!(matches!(
+            parse_output_type(Some("ndjson"))?,
+            OutputType::JsonLines
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn check_at_most_rejects_values_over_the_limit() {
+        assert!(check_at_most("n", None, 10).is_ok());
+        assert!(check_at_most("n", Some(10), 10).is_ok());
+        let Err(err) = check_at_most("max_wall_clock_seconds", Some(usize::MAX), 10) else {
+            panic!("expected values over the limit to be rejected");
+        };
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+        assert!(err.message.contains("max_wall_clock_seconds"));
+    }
+
+    #[test]
+    fn filter_summary_line_is_one_json_record() -> anyhow::Result<()> {
+        let line = filter_summary_line(FilterCounts {
+            no_speech: 2,
+            phrase: 1,
+            ..FilterCounts::default()
+        });
+        let text = std::str::from_utf8(&line)?;
+        assert_eq!(text.matches('\n').count(), 1);
+        assert!(text.ends_with('\n'));
+        let value: serde_json::Value = serde_json::from_str(text)?;
+        assert_eq!(value["status"], "summary");
+        assert_eq!(value["filtered"]["no_speech"], 2);
+        assert_eq!(value["filtered"]["low_probability"], 0);
+        assert_eq!(value["filtered"]["phrase"], 1);
         Ok(())
     }
 
@@ -431,7 +845,7 @@ mod tests {
 
     #[tokio::test]
     async fn get_prefix_bytes_errors_on_empty_body() {
-        let res = get_prefix_bytes(stream_from_chunks(vec![]), 16).await;
+        let res = get_prefix_bytes(stream_from_chunks(vec![]), None, 16).await;
         assert!(res.is_err());
         let err = res.err().expect("expected AppError");
         assert!(err.message.contains("request body was empty"));
@@ -440,7 +854,7 @@ mod tests {
     #[tokio::test]
     async fn get_prefix_bytes_skips_empty_chunks() {
         let (prefix_bytes, prefix_chunks, _tail) =
-            match get_prefix_bytes(stream_from_chunks(vec![b"", b"abc"]), 16).await {
+            match get_prefix_bytes(stream_from_chunks(vec![b"", b"abc"]), None, 16).await {
                 Ok(v) => v,
                 Err(err) => panic!("unexpected error: {}", err.message),
             };
@@ -452,7 +866,7 @@ mod tests {
     #[tokio::test]
     async fn get_prefix_bytes_splits_large_chunk_and_replays_tail() {
         let (prefix_bytes, prefix_chunks, tail) =
-            match get_prefix_bytes(stream_from_chunks(vec![b"helloWORLD"]), 5).await {
+            match get_prefix_bytes(stream_from_chunks(vec![b"helloWORLD"]), None, 5).await {
                 Ok(v) => v,
                 Err(err) => panic!("unexpected error: {}", err.message),
             };
@@ -469,14 +883,197 @@ mod tests {
         assert_eq!(tail_chunks[0].as_ref(), b"WORLD");
     }
 
+    #[tokio::test]
+    async fn get_prefix_bytes_stops_once_container_is_recognized() {
+        let wav: &'static [u8] = std::fs::read("tests/fixtures/jfk.wav")
+            .expect("read wav fixture")
+            .leak();
+        let (head, rest) = wav.split_at(4096);
+
+        let (prefix_bytes, _prefix_chunks, tail) =
+            match get_prefix_bytes(stream_from_chunks(vec![head, rest]), None, 512 * 1024).await {
+                Ok(v) => v,
+                Err(err) => panic!("unexpected error: {}", err.message),
+            };
+
+        assert_eq!(prefix_bytes, head);
+        let tail_chunks: Vec<Bytes> = match tail.try_collect().await {
+            Ok(v) => v,
+            Err(err) => panic!("unexpected tail stream error: {err}"),
+        };
+        assert_eq!(tail_chunks.concat(), rest);
+    }
+
+    #[tokio::test]
+    async fn get_prefix_bytes_probes_small_chunks_at_doubling_sizes() {
+        let wav: &'static [u8] = std::fs::read("tests/fixtures/jfk.wav")
+            .expect("read wav fixture")
+            .leak();
+        let chunks: Vec<&'static [u8]> = wav[..64 * 1024].chunks(100).collect();
+
+        let (prefix_bytes, _prefix_chunks, _tail) =
+            match get_prefix_bytes(stream_from_chunks(chunks), None, 512 * 1024).await {
+                Ok(v) => v,
+                Err(err) => panic!("unexpected error: {}", err.message),
+            };
+
+        // Not probed per chunk: the first probe waits for 4 KiB, and recognizes the WAV.
+        assert_eq!(prefix_bytes.len(), 4_100);
+    }
+
     #[test]
-    fn validate_media_prefix_accepts_wav_fixture() {
+    fn cancel_on_drop_cancels_token() {
+        let token = CancellationToken::new();
+        drop(CancelOnDrop(token.clone()));
+        assert!(token.is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn trailing_moov_upload_is_spooled_and_accepted() -> anyhow::Result<()> {
+        let bytes = Bytes::from(std::fs::read("tests/fixtures/tone-moov-at-end.mp4")?);
+        assert!(validate_media_prefix(&bytes, Some("mp4")).await.is_err());
+        assert!(is_iso_bmff(&bytes));
+
+        let body = |bytes: &Bytes| -> BodyDataStream {
+            futures_util::stream::iter(vec![Ok(bytes.slice(..1024)), Ok(bytes.slice(1024..))])
+                .boxed()
+        };
+        let Ok(UploadInput::Spooled(file)) = accept_media_upload(
+            body(&bytes),
+            Some("mp4"),
+            &TrackSelection::First,
+            bytes.len(),
+            false,
+        )
+        .await
+        else {
+            panic!("expected the upload to be spooled and accepted");
+        };
+        let mut spooled = Vec::new();
+        std::io::Read::read_to_end(&mut &file, &mut spooled)?;
+        assert_eq!(spooled, bytes);
+
+        let Err(err) = accept_media_upload(
+            body(&bytes),
+            Some("mp4"),
+            &TrackSelection::First,
+            bytes.len() - 1,
+            false,
+        )
+        .await
+        else {
+            panic!("expected the upload limit to apply while spooling");
+        };
+        assert_eq!(err.status, StatusCode::PAYLOAD_TOO_LARGE);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn replayable_upload_is_spooled_within_the_upload_limit() -> anyhow::Result<()> {
+        let bytes = Bytes::from(std::fs::read("tests/fixtures/jfk.wav")?);
+        let body = |bytes: &Bytes| -> BodyDataStream {
+            futures_util::stream::iter(vec![Ok(bytes.clone())]).boxed()
+        };
+
+        let Ok(UploadInput::Spooled(_)) = accept_media_upload(
+            body(&bytes),
+            Some("wav"),
+            &TrackSelection::First,
+            bytes.len(),
+            true,
+        )
+        .await
+        else {
+            panic!("expected a replayable upload to be spooled");
+        };
+        let Err(err) = accept_media_upload(
+            body(&bytes),
+            Some("wav"),
+            &TrackSelection::First,
+            bytes.len() - 1,
+            true,
+        )
+        .await
+        else {
+            panic!("expected the upload limit to apply while spooling");
+        };
+        assert_eq!(err.status, StatusCode::PAYLOAD_TOO_LARGE);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn upload_without_the_selected_track_is_rejected() -> anyhow::Result<()> {
+        let bytes = Bytes::from(std::fs::read("tests/fixtures/jfk.wav")?);
+        let body: BodyDataStream = futures_util::stream::iter(vec![Ok(bytes.clone())]).boxed();
+
+        let Err(err) = accept_media_upload(
+            body,
+            Some("wav"),
+            &TrackSelection::Index(3),
+            usize::MAX,
+            false,
+        )
+        .await
+        else {
+            panic!("expected a missing track to be rejected");
+        };
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+        assert!(
+            err.message.contains("available audio tracks: #0"),
+            "{}",
+            err.message
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn unrecognized_upload_is_rejected_without_spooling() {
+        // Larger than the probe window, so the rest of the body is never read.
+        let garbage = Bytes::from(vec![0x42u8; 600 * 1024]);
+        let body: BodyDataStream = futures_util::stream::iter(vec![Ok(garbage)]).boxed();
+
+        let Err(err) =
+            accept_media_upload(body, None, &TrackSelection::First, usize::MAX, false).await
+        else {
+            panic!("expected garbage to be rejected");
+        };
+        assert_eq!(err.status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+
+    #[tokio::test]
+    async fn validate_media_prefix_accepts_wav_fixture() {
         let bytes = std::fs::read("tests/fixtures/jfk.wav").expect("read wav fixture");
-        if let Err(err) = validate_media_prefix(&bytes) {
+        if let Err(err) = validate_media_prefix(&bytes, Some("wav")).await {
             panic!(
                 "expected WAV fixture to probe successfully: {}",
                 err.message
             );
         }
     }
+
+    #[tokio::test]
+    async fn validate_media_prefix_explains_unrecognized_uploads() {
+        let err = validate_media_prefix(b"not a media file", None)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert!(
+            err.message
+                .starts_with("unsupported or unrecognized media container: ")
+        );
+        assert!(err.message.contains("failed to probe media stream"));
+    }
+
+    #[test]
+    fn content_type_maps_to_format_hint() {
+        assert_eq!(format_hint_for_content_type("video/mp4"), Some("mp4"));
+        assert_eq!(
+            format_hint_for_content_type("Audio/WebM; codecs=opus"),
+            Some("webm")
+        );
+        assert_eq!(
+            format_hint_for_content_type("application/octet-stream"),
+            None
+        );
+    }
 }
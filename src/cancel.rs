@@ -0,0 +1,56 @@
+//! Cooperative cancellation for in-progress transcriptions.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// A cloneable handle used to abort an in-progress transcription.
+///
+/// Cancellation is cooperative. Once [`CancellationToken::cancel`] is called, Scribble stops at
+/// the next check point and returns [`crate::Error::Cancelled`]. Check points are:
+/// - between decoded chunks in the high-level transcription loop
+/// - before each inference pass of the incremental Whisper transcriber
+/// - inside whisper.cpp itself, via its abort callback
+///
+/// Clones share state: keep one clone to trigger cancellation and pass another via
+/// `Opts::cancellation_token`.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Create a new, non-cancelled token.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request cancellation. Idempotent; safe to call from any thread.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Whether cancellation has been requested.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_starts_uncancelled() {
+        let token = CancellationToken::new();
+        assert!(!token.is_cancelled());
+    }
+
+    #[test]
+    fn clones_share_cancellation_state() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        clone.cancel();
+        clone.cancel();
+        assert!(token.is_cancelled());
+    }
+}
@@ -0,0 +1,172 @@
+//! What a backend (or one of its models) can do.
+
+use serde::Serialize;
+
+use crate::Result;
+use crate::opts::Opts;
+use crate::transcription_mode::TranscriptionMode;
+
+/// Features a backend supports, as reported by [`crate::Backend::capabilities`].
+///
+/// [`Capabilities::default`] claims everything, which is what backends that do not describe
+/// themselves report.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Capabilities {
+    /// Whether `Opts::enable_translate_to_english` is supported.
+    pub translation: bool,
+
+    /// Whether `TranscriptionMode::Streaming` is supported.
+    pub streaming: bool,
+
+    /// Whether per-word timing (`Opts::enable_word_level_segments`) is supported.
+    pub word_timestamps: bool,
+
+    /// Language codes accepted by `Opts::language`, or `None` when any code is accepted.
+    ///
+    /// `"auto"` (auto-detect) is always accepted.
+    pub languages: Option<Vec<String>>,
+}
+
+impl Default for Capabilities {
+    fn default() -> Self {
+        Self {
+            translation: true,
+            streaming: true,
+            word_timestamps: true,
+            languages: None,
+        }
+    }
+}
+
+impl Capabilities {
+    /// Features supported by both `self` and `other`.
+    ///
+    /// Useful for combinators that may hand the same request to either of two backends.
+    pub fn intersect(&self, other: &Capabilities) -> Capabilities {
+        let languages = match (&self.languages, &other.languages) {
+            (None, None) => None,
+            (Some(languages), None) | (None, Some(languages)) => Some(languages.clone()),
+            (Some(ours), Some(theirs)) => Some(
+                ours.iter()
+                    .filter(|language| theirs.contains(language))
+                    .cloned()
+                    .collect(),
+            ),
+        };
+
+        Capabilities {
+            translation: self.translation && other.translation,
+            streaming: self.streaming && other.streaming,
+            word_timestamps: self.word_timestamps && other.word_timestamps,
+            languages,
+        }
+    }
+
+    /// Check that `opts` only asks for supported features.
+    ///
+    /// Returns [`crate::Error::InvalidInput`] naming the first unsupported option.
+    pub fn validate(&self, opts: &Opts) -> Result<()> {
+        if opts.enable_translate_to_english && !self.translation {
+            return Err(crate::Error::invalid_input(
+                "translation is not supported by the selected model",
+            ));
+        }
+
+        if opts.transcription_mode == TranscriptionMode::Streaming && !self.streaming {
+            return Err(crate::Error::invalid_input(
+                "streaming transcription is not supported by the selected model",
+            ));
+        }
+
+        if opts.enable_word_level_segments && !self.word_timestamps {
+            return Err(crate::Error::invalid_input(
+                "word-level segments are not supported by the selected model",
+            ));
+        }
+
+        if let (Some(language), Some(languages)) = (opts.language.as_deref(), &self.languages)
+            && !language.eq_ignore_ascii_case("auto")
+            && !languages.iter().any(|l| l.eq_ignore_ascii_case(language))
+        {
+            return Err(crate::Error::invalid_input(format!(
+                "language '{language}' is not supported by the selected model"
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn english_only() -> Capabilities {
+        Capabilities {
+            translation: false,
+            languages: Some(vec!["en".to_owned()]),
+            ..Capabilities::default()
+        }
+    }
+
+    #[test]
+    fn validate_rejects_unsupported_options() {
+        let caps = english_only();
+
+        let err = caps
+            .validate(&Opts {
+                enable_translate_to_english: true,
+                ..Opts::default()
+            })
+            .unwrap_err();
+        assert!(matches!(err, crate::Error::InvalidInput { .. }));
+        assert!(err.to_string().contains("translation"));
+
+        let err = caps
+            .validate(&Opts {
+                language: Some("de".to_owned()),
+                ..Opts::default()
+            })
+            .unwrap_err();
+        assert!(err.to_string().contains("language 'de'"));
+    }
+
+    #[test]
+    fn validate_accepts_supported_and_auto_language() -> anyhow::Result<()> {
+        let caps = english_only();
+
+        caps.validate(&Opts::default())?;
+        caps.validate(&Opts {
+            language: Some("EN".to_owned()),
+            ..Opts::default()
+        })?;
+        caps.validate(&Opts {
+            language: Some("auto".to_owned()),
+            ..Opts::default()
+        })?;
+        Ok(())
+    }
+
+    #[test]
+    fn intersect_keeps_only_shared_features() {
+        let multilingual = Capabilities {
+            word_timestamps: false,
+            languages: Some(vec!["de".to_owned(), "en".to_owned()]),
+            ..Capabilities::default()
+        };
+
+        assert_eq!(
+            multilingual.intersect(&english_only()),
+            Capabilities {
+                translation: false,
+                streaming: true,
+                word_timestamps: false,
+                languages: Some(vec!["en".to_owned()]),
+            }
+        );
+        assert_eq!(
+            Capabilities::default().intersect(&english_only()),
+            english_only()
+        );
+    }
+}
@@ -0,0 +1,24 @@
+/// How the incremental (streaming) transcriber decides which segments are final.
+///
+/// Each inference pass over the buffered window produces a *hypothesis*: a list of segments.
+/// Segments near the end of the window are unstable because they may be cut mid-word or revised
+/// once more audio arrives. The policy decides how much of each hypothesis to emit.
+///
+/// At end-of-stream (and when the buffer reaches `Opts::incremental_max_buffer_seconds`) every
+/// policy flushes the full hypothesis so output is never lost.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitPolicy {
+    /// Emit all but the last segment of every hypothesis.
+    ///
+    /// Lowest latency, but emitted text can differ from what a later pass would produce.
+    AllButLast,
+
+    /// Emit only the leading words that `n` consecutive hypotheses agree on (LocalAgreement-n).
+    ///
+    /// Trades latency for stability: a word is emitted once it is unchanged across `n` passes,
+    /// which avoids committing words cut at the window boundary. Agreement is checked word by
+    /// word, so a segment whose tail is still changing is cut at the last agreed word (when the
+    /// backend reports word timings) and the rest stays pending. Values of `n` below `2` are
+    /// treated as `2`.
+    LocalAgreement { n: usize },
+}
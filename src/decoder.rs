@@ -8,20 +8,30 @@
 //! - `decode` handles codec decoding
 //! - `audio_pipeline` handles PCM normalization (downmix + resample) + chunking
 //!
-//! Current mode: **unseekable** (`Read` only) via `ReadOnlySource`.
-//! This works well for stdin / sockets / HTTP bodies and stream-friendly container layouts.
-//! If you later want to support seekable inputs (many MP4/MOV files), add a
-//! `decode_to_stream_from_reader(Read + Seek)` variant using a seekable `MediaSource`.
+//! Two input modes are supported:
+//! - **unseekable** (`Read` only) via `ReadOnlySource`: stdin / sockets / HTTP bodies and
+//!   stream-friendly container layouts.
+//! - **seekable** (`Read + Seek`): regular files. Required by containers that keep their metadata
+//!   at the end (many MP4/MOV files with a trailing `moov` box), and lets the decoder seek to
+//!   `StreamDecodeOpts::start_seconds` instead of decoding from the beginning.
+//!
+//! Headerless PCM (`StreamDecodeOpts::raw_format`) skips `demux`/`decode` and goes straight to
+//! `audio_pipeline`, in either mode.
 
-use std::io::Read;
-use std::sync::Mutex;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::sync::{Arc, Mutex, mpsc};
+use std::time::Duration;
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use symphonia::core::io::{MediaSource, ReadOnlySource};
 
-use crate::audio_pipeline::AudioPipeline;
+use crate::audio_pipeline::{AudioPipeline, TrackMixer};
 use crate::decode::{decode_packet_and_then, make_decoder_for_track};
-use crate::demux::{next_packet, probe_source_and_pick_default_track};
+use crate::demux::{
+    next_packet, probe_source_and_select_tracks, seek_to_seconds, track_duration_seconds,
+};
+use crate::raw_audio::RawAudioFormat;
+use crate::track_selection::TrackSelection;
 
 /// Consumer callback for decoded samples.
 ///
@@ -29,6 +39,17 @@ use crate::demux::{next_packet, probe_source_and_pick_default_track};
 /// Returning `Ok(false)` signals "stop decoding early".
 pub trait SamplesSink {
     fn on_samples(&mut self, samples_16k_mono: &[f32]) -> Result<bool>;
+
+    /// Called once after probing when the container reports the selected tracks' duration.
+    ///
+    /// Defaults to a no-op; sinks that report progress use it as the expected total.
+    fn on_total_duration(&mut self, _total_seconds: f64) {}
+
+    /// Called before the first samples when the decoder seeked to `StreamDecodeOpts::start_seconds`.
+    ///
+    /// The first emitted sample then lies at `position_seconds` in the source rather than at 0.
+    /// Defaults to a no-op.
+    fn on_seek(&mut self, _position_seconds: f64) {}
 }
 
 /// Streaming decode configuration.
@@ -44,6 +65,24 @@ pub struct StreamDecodeOpts {
     /// Optional container hint (e.g. "mp4", "ts", "webm", "mkv", "ogg").
     /// This can improve probing, especially for unseekable streams.
     pub hint_extension: Option<String>,
+
+    /// Where the caller wants decoding to start (seconds into the source).
+    ///
+    /// Seekable sources are seeked to at or before this point and report it via
+    /// [`SamplesSink::on_seek`]. Other sources decode from the beginning; skipping the audio
+    /// before the start is left to the sink.
+    pub start_seconds: Option<f64>,
+
+    /// Which audio track(s) to decode. Several tracks are mixed into one stream.
+    pub track: TrackSelection,
+
+    /// Source channel (zero-based) to decode instead of downmixing every channel to mono.
+    pub channel: Option<usize>,
+
+    /// Treat the input as headerless PCM in this format instead of probing for a container.
+    ///
+    /// `hint_extension` is ignored, and the input counts as a single track.
+    pub raw_format: Option<RawAudioFormat>,
 }
 
 impl Default for StreamDecodeOpts {
@@ -51,6 +90,10 @@ impl Default for StreamDecodeOpts {
         Self {
             target_chunk_frames: 1024,
             hint_extension: None,
+            start_seconds: None,
+            track: TrackSelection::First,
+            channel: None,
+            raw_format: None,
         }
     }
 }
@@ -68,6 +111,10 @@ pub fn decode_to_stream_from_read<R>(
 where
     R: Read + Send + 'static,
 {
+    if let Some(format) = opts.raw_format {
+        return decode_raw(reader, format, &opts, sink);
+    }
+
     // Symphonia's `MediaSource` is `Read + Send + Sync`. This only needs to *move* the reader to
     // the decode thread (not share it concurrently), so it is wrapped in a mutex to satisfy
     // `Sync`.
@@ -75,49 +122,423 @@ where
     decode_impl(Box::new(source), opts, sink)
 }
 
+/// Decode a seekable input and emit normalized chunks into `sink`.
+///
+/// Unlike [`decode_to_stream_from_read`], this handles containers whose metadata sits after the
+/// media data (e.g. MP4/MOV with a trailing `moov` box).
+pub fn decode_to_stream_from_reader<R>(
+    reader: R,
+    opts: StreamDecodeOpts,
+    sink: &mut dyn SamplesSink,
+) -> Result<()>
+where
+    R: Read + Seek + Send + 'static,
+{
+    if let Some(format) = opts.raw_format {
+        return decode_raw_seekable(reader, format, &opts, sink);
+    }
+
+    let source = SeekableSource::new(reader)?;
+    decode_impl(Box::new(source), opts, sink)
+}
+
+/// Decoder input, either a plain byte stream or a seekable one.
+///
+/// Lets callers pick the input mode up front and hand it to code that is not generic over the
+/// reader type (e.g. a decode thread).
+pub(crate) enum DecodeInput {
+    Stream(Box<dyn Read + Send>),
+    Seekable(Box<dyn ReadSeek + Send>),
+}
+
+impl DecodeInput {
+    /// Wrap the input in the Symphonia `MediaSource` for its mode.
+    pub(crate) fn into_media_source(self) -> Result<Box<dyn MediaSource>> {
+        Ok(match self {
+            Self::Stream(reader) => Box::new(ReadOnlySource::new(LockedRead::new(reader))),
+            Self::Seekable(reader) => Box::new(SeekableSource::new(reader)?),
+        })
+    }
+}
+
+/// `Read + Seek`, usable as a single trait object.
+pub(crate) trait ReadSeek: Read + Seek {}
+
+impl<T: Read + Seek> ReadSeek for T {}
+
+/// Decode `input` with [`decode_to_stream_from_read`] or [`decode_to_stream_from_reader`].
+pub(crate) fn decode_to_stream(
+    input: DecodeInput,
+    opts: StreamDecodeOpts,
+    sink: &mut dyn SamplesSink,
+) -> Result<()> {
+    match input {
+        DecodeInput::Stream(reader) => decode_to_stream_from_read(reader, opts, sink),
+        DecodeInput::Seekable(reader) => decode_to_stream_from_reader(reader, opts, sink),
+    }
+}
+
+/// Largest plain stream [`ReplayableInput::new`] copies to a temporary file (4 GiB).
+pub(crate) const MAX_SPOOL_BYTES: u64 = 4 << 30;
+
+/// Block size for copying a plain stream to a temporary file.
+const SPOOL_BLOCK_BYTES: usize = 64 * 1024;
+
+/// How often the copy checks `keep_reading` while the input is stalled.
+const SPOOL_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// An input that can be decoded more than once (e.g. once per channel).
+///
+/// Seekable inputs are rewound between passes; plain streams are first copied to an anonymous
+/// temporary file.
+pub(crate) struct ReplayableInput {
+    inner: Arc<Mutex<Box<dyn ReadSeek + Send>>>,
+    start: u64,
+}
+
+impl ReplayableInput {
+    /// Wrap `input`, copying a plain stream to a temporary file first.
+    ///
+    /// The copy fails once the stream exceeds `max_bytes`. `keep_reading` is polled between
+    /// blocks and while the stream is stalled: `Ok(false)` ends the copy early, keeping what was
+    /// read so far, and an error aborts it. After an early end the reader thread is detached
+    /// rather than joined, since it may be blocked on a stalled input.
+    pub(crate) fn new(
+        input: DecodeInput,
+        max_bytes: u64,
+        keep_reading: impl FnMut() -> Result<bool>,
+    ) -> Result<Self> {
+        let mut reader: Box<dyn ReadSeek + Send> = match input {
+            DecodeInput::Seekable(reader) => reader,
+            DecodeInput::Stream(reader) => Box::new(spool(reader, max_bytes, keep_reading)?),
+        };
+        let start = reader
+            .stream_position()
+            .context("failed to query input position")?;
+
+        Ok(Self {
+            inner: Arc::new(Mutex::new(reader)),
+            start,
+        })
+    }
+
+    /// A seekable handle positioned at the start of the input.
+    ///
+    /// Handles share one underlying reader, so only one pass may read at a time.
+    pub(crate) fn rewound(&self) -> Result<DecodeInput> {
+        self.inner
+            .lock()
+            .map_err(|_| anyhow::anyhow!("decoder input mutex poisoned"))?
+            .seek(SeekFrom::Start(self.start))
+            .context("failed to rewind the input")?;
+        Ok(DecodeInput::Seekable(Box::new(SharedReader(Arc::clone(
+            &self.inner,
+        )))))
+    }
+
+    /// Number of channels in the track `opts.track` selects.
+    pub(crate) fn channel_count(&self, opts: &StreamDecodeOpts) -> Result<usize> {
+        if let Some(format) = opts.raw_format {
+            return Ok(format.channels());
+        }
+
+        let DecodeInput::Seekable(reader) = self.rewound()? else {
+            unreachable!("replayable inputs are seekable");
+        };
+        let source = SeekableSource::new(reader)?;
+        let (_, tracks) = probe_source_and_select_tracks(
+            Box::new(source),
+            opts.hint_extension.as_deref(),
+            &opts.track,
+        )?;
+        tracks[0]
+            .codec_params
+            .channels
+            .map(|channels| channels.count())
+            .ok_or_else(|| {
+                anyhow::anyhow!("the selected audio track does not report its channel layout")
+            })
+    }
+}
+
+/// Copy `reader` to an anonymous temporary file; see [`ReplayableInput::new`].
+fn spool(
+    mut reader: Box<dyn Read + Send>,
+    max_bytes: u64,
+    mut keep_reading: impl FnMut() -> Result<bool>,
+) -> Result<std::fs::File> {
+    let mut file = tempfile::tempfile().context("failed to create a temporary file")?;
+
+    // Read on a separate thread so a stalled input cannot block the limit checks.
+    let (tx, rx) = mpsc::sync_channel::<std::io::Result<Vec<u8>>>(4);
+    std::thread::spawn(move || {
+        loop {
+            let mut block = vec![0u8; SPOOL_BLOCK_BYTES];
+            let read = match reader.read(&mut block) {
+                Ok(0) => return,
+                Ok(n) => Ok(n),
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(err) => Err(err),
+            };
+            let failed = read.is_err();
+            if tx.send(read.map(|n| block[..n].to_vec())).is_err() || failed {
+                return;
+            }
+        }
+    });
+
+    let mut written = 0u64;
+    while keep_reading()? {
+        let block = match rx.recv_timeout(SPOOL_POLL_INTERVAL) {
+            Ok(block) => block.context("failed to read the input")?,
+            Err(mpsc::RecvTimeoutError::Timeout) => continue,
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        };
+        written = written.saturating_add(block.len() as u64);
+        if written > max_bytes {
+            bail!("the input is larger than the {max_bytes}-byte limit for replaying it");
+        }
+        file.write_all(&block)
+            .context("failed to copy the input to a temporary file")?;
+    }
+
+    file.rewind()
+        .context("failed to rewind the temporary file")?;
+    Ok(file)
+}
+
+/// A `Read + Seek` handle onto a [`ReplayableInput`].
+struct SharedReader(Arc<Mutex<Box<dyn ReadSeek + Send>>>);
+
+impl Read for SharedReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.0
+            .lock()
+            .map_err(|_| std::io::Error::other("decoder input mutex poisoned"))?
+            .read(buf)
+    }
+}
+
+impl Seek for SharedReader {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.0
+            .lock()
+            .map_err(|_| std::io::Error::other("decoder input mutex poisoned"))?
+            .seek(pos)
+    }
+}
+
 /// Shared implementation that takes an abstract Symphonia `MediaSource`.
 fn decode_impl(
     source: Box<dyn MediaSource>,
     opts: StreamDecodeOpts,
     sink: &mut dyn SamplesSink,
 ) -> Result<()> {
-    let (mut format, track) =
-        probe_source_and_pick_default_track(source, opts.hint_extension.as_deref())?;
+    let seekable = source.is_seekable();
+    let (mut format, tracks) =
+        probe_source_and_select_tracks(source, opts.hint_extension.as_deref(), &opts.track)?;
 
-    let mut decoder = make_decoder_for_track(&track)?;
-    let mut pipeline = AudioPipeline::new();
+    if let Some(total_seconds) = tracks
+        .iter()
+        .filter_map(track_duration_seconds)
+        .reduce(f64::max)
+    {
+        sink.on_total_duration(total_seconds);
+    }
+
+    // The container seeks every track together; the first one's position stands for all.
+    if let Some(start_seconds) = opts.start_seconds
+        && start_seconds > 0.0
+        && seekable
+        && let Some(position_seconds) = seek_to_seconds(&mut format, &tracks[0], start_seconds)
+    {
+        sink.on_seek(position_seconds);
+    }
+
+    let mut lanes = tracks
+        .iter()
+        .map(|track| {
+            Ok(TrackLane {
+                track_id: track.id,
+                decoder: make_decoder_for_track(track)?,
+                pipeline: opts
+                    .channel
+                    .map_or_else(AudioPipeline::new, AudioPipeline::for_channel),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    // Only needed when several tracks are mixed; a single track goes straight to the sink.
+    let mut mixer = (lanes.len() > 1).then(|| TrackMixer::new(lanes.len()));
+    let frames = opts.target_chunk_frames;
 
     loop {
         let Some(packet) = next_packet(&mut format)? else {
             break;
         };
 
-        // Ignore packets from non-audio tracks.
-        if packet.track_id() != track.id {
+        // Ignore packets from tracks that were not selected (video, other audio, ...).
+        let Some(lane_index) = lanes.iter().position(|l| l.track_id == packet.track_id()) else {
             continue;
-        }
+        };
+        let lane = &mut lanes[lane_index];
 
-        // Decode packet → normalized audio pipeline → emit chunks.
+        // Decode packet → normalized audio pipeline → emit chunks (or buffer them for mixing).
         //
         // `decode_packet_and_then` returns `Ok(false)` for recoverable cases
         // (e.g. bad frames / IO end). Keep iterating.
-        decode_packet_and_then(&mut decoder, &packet, |decoded| {
-            pipeline
-                .push_decoded_and_emit(&decoded, opts.target_chunk_frames, |chunk| {
-                    sink.on_samples(chunk)
+        let mut keep_going = true;
+        decode_packet_and_then(&mut lane.decoder, &packet, |decoded| {
+            keep_going = lane
+                .pipeline
+                .push_decoded_and_emit(&decoded, frames, |chunk| match mixer.as_mut() {
+                    Some(mixer) => {
+                        mixer.push(lane_index, chunk);
+                        Ok(true)
+                    }
+                    None => sink.on_samples(chunk),
                 })
-                .context("audio pipeline failed while processing decoded samples")
+                .context("audio pipeline failed while processing decoded samples")?;
+            Ok(())
         })?;
+        if let Some(mixer) = mixer.as_mut() {
+            keep_going = mixer.emit_mixed(frames, false, |chunk| sink.on_samples(chunk))?;
+        }
+
+        // The sink asked to stop; skip the rest of the input and the resampler tail.
+        if !keep_going {
+            return Ok(());
+        }
+    }
+
+    // Flush any buffered resampler tail (and, when mixing, whatever the mixer still holds).
+    for (lane_index, lane) in lanes.iter_mut().enumerate() {
+        let keep_going = lane
+            .pipeline
+            .finalize(frames, |chunk| match mixer.as_mut() {
+                Some(mixer) => {
+                    mixer.push(lane_index, chunk);
+                    Ok(true)
+                }
+                None => sink.on_samples(chunk),
+            })
+            .context("audio pipeline failed during finalize")?;
+        if !keep_going {
+            return Ok(());
+        }
+    }
+    if let Some(mixer) = mixer.as_mut() {
+        mixer.emit_mixed(frames, true, |chunk| sink.on_samples(chunk))?;
+    }
+
+    Ok(())
+}
+
+/// Bytes read from a raw PCM input per call (100 ms of 16 kHz stereo s16le).
+const RAW_READ_BYTES: usize = 6_400;
+
+/// Decode headerless PCM from `reader`, bypassing container probing.
+fn decode_raw(
+    mut reader: impl Read,
+    format: RawAudioFormat,
+    opts: &StreamDecodeOpts,
+    sink: &mut dyn SamplesSink,
+) -> Result<()> {
+    if !matches!(
+        opts.track,
+        TrackSelection::First | TrackSelection::All | TrackSelection::Index(0)
+    ) {
+        bail!(
+            "raw PCM input has a single audio track; '{}' does not match it",
+            opts.track
+        );
+    }
+
+    let frame_bytes = format.bytes_per_frame();
+    let frames = opts.target_chunk_frames;
+    let mut pipeline = opts
+        .channel
+        .map_or_else(AudioPipeline::new, AudioPipeline::for_channel);
+
+    // Read a fixed number of bytes at a time (at least one frame); a partial frame at the end of
+    // a read waits for the next one.
+    let mut buf = vec![0u8; RAW_READ_BYTES.max(frame_bytes)];
+    let mut pending = 0;
+    let mut samples = Vec::new();
+    loop {
+        let n = match reader.read(&mut buf[pending..]) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err).context("failed to read raw audio input"),
+        };
+        let filled = pending + n;
+        let whole = filled - filled % frame_bytes;
+
+        samples.clear();
+        format.decode_into(&buf[..whole], &mut samples);
+        let keep_going = pipeline
+            .push_interleaved_and_emit(
+                &samples,
+                format.sample_rate(),
+                format.channels(),
+                frames,
+                |chunk| sink.on_samples(chunk),
+            )
+            .context("audio pipeline failed while processing raw samples")?;
+        if !keep_going {
+            return Ok(());
+        }
+
+        buf.copy_within(whole..filled, 0);
+        pending = filled - whole;
     }
 
-    // Flush any buffered resampler tail.
     pipeline
-        .finalize(opts.target_chunk_frames, |chunk| sink.on_samples(chunk))
+        .finalize(frames, |chunk| sink.on_samples(chunk))
         .context("audio pipeline failed during finalize")?;
-
     Ok(())
 }
 
+/// [`decode_raw`] for a seekable input: reports the total duration and seeks straight to
+/// `opts.start_seconds`.
+fn decode_raw_seekable(
+    mut reader: impl Read + Seek,
+    format: RawAudioFormat,
+    opts: &StreamDecodeOpts,
+    sink: &mut dyn SamplesSink,
+) -> Result<()> {
+    let frame_bytes = format.bytes_per_frame() as u64;
+    let sample_rate = f64::from(format.sample_rate());
+    let start = reader
+        .stream_position()
+        .context("failed to query input position")?;
+    let end = reader
+        .seek(SeekFrom::End(0))
+        .context("failed to seek to the end of the input")?;
+    let total_frames = end.saturating_sub(start) / frame_bytes;
+    sink.on_total_duration(total_frames as f64 / sample_rate);
+
+    let mut first_frame = 0;
+    if let Some(start_seconds) = opts.start_seconds
+        && start_seconds > 0.0
+    {
+        first_frame = ((start_seconds * sample_rate) as u64).min(total_frames);
+        sink.on_seek(first_frame as f64 / sample_rate);
+    }
+    reader
+        .seek(SeekFrom::Start(start + first_frame * frame_bytes))
+        .context("failed to seek in the input")?;
+
+    decode_raw(reader, format, opts, sink)
+}
+
+/// Decoding state for one selected track.
+struct TrackLane {
+    track_id: u32,
+    decoder: Box<dyn symphonia::core::codecs::Decoder>,
+    pipeline: AudioPipeline,
+}
+
 struct LockedRead<R> {
     inner: Mutex<R>,
 }
@@ -139,6 +560,62 @@ impl<R: Read> Read for LockedRead<R> {
     }
 }
 
+impl<R: Seek> Seek for LockedRead<R> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.inner
+            .lock()
+            .map_err(|_| std::io::Error::other("decoder input mutex poisoned"))?
+            .seek(pos)
+    }
+}
+
+/// Seekable `MediaSource` over any `Read + Seek` input.
+struct SeekableSource<R> {
+    inner: LockedRead<R>,
+    byte_len: u64,
+}
+
+impl<R: Read + Seek> SeekableSource<R> {
+    fn new(mut inner: R) -> Result<Self> {
+        let position = inner
+            .stream_position()
+            .context("failed to query input position")?;
+        let byte_len = inner
+            .seek(SeekFrom::End(0))
+            .context("failed to seek to the end of the input")?;
+        inner
+            .seek(SeekFrom::Start(position))
+            .context("failed to rewind the input")?;
+
+        Ok(Self {
+            inner: LockedRead::new(inner),
+            byte_len,
+        })
+    }
+}
+
+impl<R: Read> Read for SeekableSource<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl<R: Seek> Seek for SeekableSource<R> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+impl<R: Read + Seek + Send> MediaSource for SeekableSource<R> {
+    fn is_seekable(&self) -> bool {
+        true
+    }
+
+    fn byte_len(&self) -> Option<u64> {
+        Some(self.byte_len)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -178,4 +655,221 @@ mod tests {
         let res = decode_to_stream_from_read(reader, StreamDecodeOpts::default(), &mut NoopSink);
         assert!(res.is_err());
     }
+
+    #[test]
+    fn decoder_stops_when_sink_asks_to() -> anyhow::Result<()> {
+        struct StopAfterFirst(usize);
+        impl SamplesSink for StopAfterFirst {
+            fn on_samples(&mut self, _samples_16k_mono: &[f32]) -> Result<bool> {
+                self.0 += 1;
+                Ok(false)
+            }
+        }
+
+        let input = std::fs::File::open("tests/fixtures/jfk.wav")?;
+        let mut sink = StopAfterFirst(0);
+        decode_to_stream_from_read(input, StreamDecodeOpts::default(), &mut sink)?;
+
+        assert_eq!(sink.0, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn decoder_lists_tracks_when_selection_does_not_match() -> anyhow::Result<()> {
+        struct NoopSink;
+        impl SamplesSink for NoopSink {
+            fn on_samples(&mut self, _samples_16k_mono: &[f32]) -> Result<bool> {
+                Ok(true)
+            }
+        }
+
+        let opts = StreamDecodeOpts {
+            track: TrackSelection::Index(1),
+            ..StreamDecodeOpts::default()
+        };
+        let input = std::fs::File::open("tests/fixtures/jfk.wav")?;
+        let err = decode_to_stream_from_read(input, opts, &mut NoopSink).unwrap_err();
+
+        assert!(err.to_string().contains("no audio track matches '1'"));
+        assert!(
+            err.to_string()
+                .contains("#0 und, pcm_s16le, 16000 Hz, 1 ch")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn decoder_seeks_seekable_sources_to_the_start_offset() -> anyhow::Result<()> {
+        #[derive(Default)]
+        struct Recorder {
+            seeked_to: Option<f64>,
+            samples: usize,
+        }
+        impl SamplesSink for Recorder {
+            fn on_samples(&mut self, samples_16k_mono: &[f32]) -> Result<bool> {
+                self.samples += samples_16k_mono.len();
+                Ok(true)
+            }
+
+            fn on_seek(&mut self, position_seconds: f64) {
+                self.seeked_to = Some(position_seconds);
+            }
+        }
+
+        let input = std::fs::File::open("tests/fixtures/jfk.wav")?;
+        let opts = StreamDecodeOpts {
+            start_seconds: Some(2.0),
+            ..StreamDecodeOpts::default()
+        };
+        let mut sink = Recorder::default();
+        decode_to_stream_from_reader(input, opts, &mut sink)?;
+
+        let seeked_to = sink.seeked_to.expect("expected a seek");
+        assert!((0.0..=2.0).contains(&seeked_to));
+        // jfk.wav is 8.5s; only the audio after the seek point is decoded.
+        let expected = ((8.5 - seeked_to) * 16_000.0) as usize;
+        assert!(sink.samples.abs_diff(expected) < 1_024);
+        Ok(())
+    }
+
+    #[test]
+    fn trailing_moov_needs_a_seekable_input() -> anyhow::Result<()> {
+        #[derive(Default)]
+        struct Count(usize);
+        impl SamplesSink for Count {
+            fn on_samples(&mut self, samples_16k_mono: &[f32]) -> Result<bool> {
+                self.0 += samples_16k_mono.len();
+                Ok(true)
+            }
+        }
+
+        // 2s of 16 kHz PCM with the `moov` box after `mdat`.
+        let path = "tests/fixtures/tone-moov-at-end.mp4";
+
+        let streamed = decode_to_stream_from_read(
+            std::fs::File::open(path)?,
+            StreamDecodeOpts::default(),
+            &mut Count::default(),
+        );
+        assert!(streamed.is_err());
+
+        let mut sink = Count::default();
+        decode_to_stream_from_reader(
+            std::fs::File::open(path)?,
+            StreamDecodeOpts::default(),
+            &mut sink,
+        )?;
+        assert_eq!(sink.0, 32_000);
+        Ok(())
+    }
+
+    #[test]
+    fn raw_pcm_bypasses_probing_and_keeps_partial_frames() -> anyhow::Result<()> {
+        /// Returns at most 3 bytes per read, so frames straddle reads.
+        struct Trickle(std::io::Cursor<Vec<u8>>);
+        impl Read for Trickle {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                let len = buf.len().min(3);
+                self.0.read(&mut buf[..len])
+            }
+        }
+        #[derive(Default)]
+        struct Collect(Vec<f32>);
+        impl SamplesSink for Collect {
+            fn on_samples(&mut self, samples_16k_mono: &[f32]) -> Result<bool> {
+                self.0.extend_from_slice(samples_16k_mono);
+                Ok(true)
+            }
+        }
+
+        // 100 frames of 16 kHz stereo s16le: left at half scale, right silent.
+        let bytes: Vec<u8> = (0..100)
+            .flat_map(|_| [16_384i16.to_le_bytes(), 0i16.to_le_bytes()].concat())
+            .collect();
+        let opts = StreamDecodeOpts {
+            raw_format: Some("s16le:16000:2".parse()?),
+            ..StreamDecodeOpts::default()
+        };
+        let mut sink = Collect::default();
+        decode_to_stream_from_read(Trickle(std::io::Cursor::new(bytes)), opts, &mut sink)?;
+
+        assert_eq!(sink.0, vec![0.25; 100]);
+        Ok(())
+    }
+
+    #[test]
+    fn raw_pcm_seeks_seekable_inputs() -> anyhow::Result<()> {
+        #[derive(Default)]
+        struct Recorder {
+            total: Option<f64>,
+            seeked_to: Option<f64>,
+            samples: usize,
+        }
+        impl SamplesSink for Recorder {
+            fn on_samples(&mut self, samples_16k_mono: &[f32]) -> Result<bool> {
+                self.samples += samples_16k_mono.len();
+                Ok(true)
+            }
+
+            fn on_total_duration(&mut self, total_seconds: f64) {
+                self.total = Some(total_seconds);
+            }
+
+            fn on_seek(&mut self, position_seconds: f64) {
+                self.seeked_to = Some(position_seconds);
+            }
+        }
+
+        // 1s of 8 kHz mono µ-law silence, resampled to 16 kHz.
+        let input = std::io::Cursor::new(vec![0xFFu8; 8_000]);
+        let opts = StreamDecodeOpts {
+            raw_format: Some("ulaw:8000:1".parse()?),
+            start_seconds: Some(0.25),
+            ..StreamDecodeOpts::default()
+        };
+        let mut sink = Recorder::default();
+        decode_to_stream_from_reader(input, opts, &mut sink)?;
+
+        assert_eq!(sink.total, Some(1.0));
+        assert_eq!(sink.seeked_to, Some(0.25));
+        // The resampler's block size rounds the flushed tail up.
+        assert!(sink.samples.abs_diff(12_000) < 1_024, "{}", sink.samples);
+        Ok(())
+    }
+
+    #[test]
+    fn replayable_input_rejects_streams_over_the_byte_limit() {
+        let input = DecodeInput::Stream(Box::new(std::io::Cursor::new(vec![0u8; 1_000])));
+
+        let err = ReplayableInput::new(input, 999, || Ok(true))
+            .err()
+            .expect("over the limit");
+        assert!(err.to_string().contains("999-byte limit"), "{err}");
+    }
+
+    #[test]
+    fn replayable_input_stops_copying_when_asked() -> anyhow::Result<()> {
+        struct Stalled;
+        impl Read for Stalled {
+            fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+                loop {
+                    std::thread::park();
+                }
+            }
+        }
+
+        let mut polls = 0;
+        let replay = ReplayableInput::new(DecodeInput::Stream(Box::new(Stalled)), 1_000, || {
+            polls += 1;
+            Ok(polls < 3)
+        })?;
+
+        let DecodeInput::Seekable(mut reader) = replay.rewound()? else {
+            unreachable!("replayable inputs are seekable");
+        };
+        let mut copied = Vec::new();
+        reader.read_to_end(&mut copied)?;
+        assert!(copied.is_empty());
+        Ok(())
+    }
 }
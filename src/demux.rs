@@ -6,29 +6,47 @@
 //! rest of the decode/transcode pipeline.
 //!
 //! Responsibilities:
-//! - Probe a `MediaSource` and select a reasonable default audio track
+//! - Probe a `MediaSource` and select the requested audio track(s)
 //! - Provide a `next_packet` helper that treats IO errors as end-of-stream
+//! - Seek to a start offset when the source allows it
 
 use anyhow::{Context, Result, anyhow};
 use symphonia::core::codecs::CODEC_TYPE_NULL;
 use symphonia::core::errors::Error as SymphoniaError;
-use symphonia::core::formats::{FormatOptions, FormatReader, Packet, Track};
+use symphonia::core::formats::{FormatOptions, FormatReader, Packet, SeekMode, SeekTo, Track};
 use symphonia::core::io::{MediaSource, MediaSourceStream, MediaSourceStreamOptions};
 use symphonia::core::meta::MetadataOptions;
-use symphonia::core::probe::Hint;
+use symphonia::core::probe::{Hint, ProbeResult};
+use symphonia::core::units::Time;
 
-/// Probe the container and pick a default audio track.
+use crate::track_selection::TrackSelection;
+
+/// Probe the container and select the audio track(s) to decode.
 ///
-/// Track selection policy:
-/// - choose the first track that looks decodable (codec != NULL)
-/// - and has a known sample rate (required for resampling decisions downstream)
+/// Only tracks that look decodable (codec != NULL) and have a known sample rate (required for
+/// resampling decisions downstream) are candidates; see [`audio_tracks`]. Fails with a list of
+/// those tracks when `selection` matches none of them.
 ///
 /// `hint_extension` can improve probe accuracy for ambiguous/unseekable inputs
 /// (e.g. "mp4", "ts", "webm", "mkv", "ogg").
-pub fn probe_source_and_pick_default_track(
+pub fn probe_source_and_select_tracks(
     source: Box<dyn MediaSource>,
     hint_extension: Option<&str>,
-) -> Result<(Box<dyn FormatReader>, Track)> {
+    selection: &TrackSelection,
+) -> Result<(Box<dyn FormatReader>, Vec<Track>)> {
+    let format = probe_source(source, hint_extension)?.format;
+    let tracks = select_tracks(&audio_tracks(format.as_ref()), selection)?;
+
+    Ok((format, tracks))
+}
+
+/// Probe the container, reading only as far as its headers.
+///
+/// The result also carries any metadata found ahead of the container (e.g. ID3 tags).
+pub fn probe_source(
+    source: Box<dyn MediaSource>,
+    hint_extension: Option<&str>,
+) -> Result<ProbeResult> {
     let mss_opts = MediaSourceStreamOptions {
         // Symphonia expects a power-of-two buffer > 32KiB for good probing behavior.
         buffer_len: 256 * 1024,
@@ -44,21 +62,119 @@ pub fn probe_source_and_pick_default_track(
     let format_opts: FormatOptions = Default::default();
     let metadata_opts: MetadataOptions = Default::default();
 
-    let probed = symphonia::default::get_probe()
+    symphonia::default::get_probe()
         .format(&hint, mss, &format_opts, &metadata_opts)
         .map_err(|e| anyhow!(e))
-        .context("failed to probe media stream")?;
-
-    let format = probed.format;
+        .context("failed to probe media stream")
+}
 
-    let track = format
+/// The container's decodable audio tracks, in container order.
+///
+/// [`TrackSelection::Index`] indexes into this list.
+pub fn audio_tracks(format: &dyn FormatReader) -> Vec<Track> {
+    format
         .tracks()
         .iter()
-        .find(|t| t.codec_params.codec != CODEC_TYPE_NULL && t.codec_params.sample_rate.is_some())
+        .filter(|t| t.codec_params.codec != CODEC_TYPE_NULL && t.codec_params.sample_rate.is_some())
         .cloned()
-        .ok_or_else(|| anyhow!("no audio track found"))?;
+        .collect()
+}
+
+/// Pick the tracks `selection` asks for out of `tracks` (as returned by [`audio_tracks`]).
+pub fn select_tracks(tracks: &[Track], selection: &TrackSelection) -> Result<Vec<Track>> {
+    if tracks.is_empty() {
+        return Err(anyhow!("no audio track found"));
+    }
+
+    let selected = match selection {
+        TrackSelection::First => tracks.first(),
+        TrackSelection::Index(index) => tracks.get(*index),
+        TrackSelection::Language(language) => tracks.iter().find(|t| {
+            t.language
+                .as_deref()
+                .is_some_and(|l| l.eq_ignore_ascii_case(language))
+        }),
+        TrackSelection::All => return Ok(tracks.to_vec()),
+    };
+
+    match selected {
+        Some(track) => Ok(vec![track.clone()]),
+        None => {
+            let available: Vec<String> = tracks
+                .iter()
+                .enumerate()
+                .map(|(index, track)| describe_track(index, track))
+                .collect();
+            Err(anyhow!(
+                "no audio track matches '{selection}'; available audio tracks: {}",
+                available.join("; ")
+            ))
+        }
+    }
+}
+
+/// One-line summary of an audio track, e.g. `#1 eng, aac, 48000 Hz, 2 ch`.
+pub fn describe_track(index: usize, track: &Track) -> String {
+    let params = &track.codec_params;
+    let mut description = format!(
+        "#{index} {}, {}",
+        track.language.as_deref().unwrap_or("und"),
+        codec_name(track)
+    );
+    if let Some(sample_rate) = params.sample_rate {
+        description.push_str(&format!(", {sample_rate} Hz"));
+    }
+    if let Some(channels) = params.channels {
+        description.push_str(&format!(", {} ch", channels.count()));
+    }
+    description
+}
 
-    Ok((format, track))
+/// Short name of `track`'s codec (e.g. `aac`, `pcm_s16le`).
+pub fn codec_name(track: &Track) -> &'static str {
+    symphonia::default::get_codecs()
+        .get_codec(track.codec_params.codec)
+        .map_or("unknown codec", |descriptor| descriptor.short_name)
+}
+
+/// Total duration of `track` in seconds, when the container reports it.
+///
+/// Returns `None` for live/unbounded streams and containers that omit frame counts.
+pub fn track_duration_seconds(track: &Track) -> Option<f64> {
+    let n_frames = track.codec_params.n_frames?;
+    let sample_rate = track.codec_params.sample_rate?;
+    if sample_rate == 0 {
+        return None;
+    }
+    Some(n_frames as f64 / sample_rate as f64)
+}
+
+/// Seek `format` to at or before `seconds` on `track`.
+///
+/// Returns the position (seconds) decoding resumes from, or `None` if the container could not
+/// seek; callers then decode from the beginning instead.
+pub fn seek_to_seconds(
+    format: &mut Box<dyn FormatReader>,
+    track: &Track,
+    seconds: f64,
+) -> Option<f64> {
+    let seeked = format
+        .seek(
+            SeekMode::Accurate,
+            SeekTo::Time {
+                time: Time::from(seconds),
+                track_id: Some(track.id),
+            },
+        )
+        .inspect_err(|err| tracing::debug!(error = %err, "seek failed; decoding from the start"))
+        .ok()?;
+
+    if let Some(time_base) = track.codec_params.time_base {
+        let time = time_base.calc_time(seeked.actual_ts);
+        return Some(time.seconds as f64 + time.frac);
+    }
+    let sample_rate = track.codec_params.sample_rate?;
+    Some(seeked.actual_ts as f64 / sample_rate as f64)
 }
 
 /// Read the next packet, treating IO errors as "end of stream".
@@ -73,3 +189,63 @@ pub fn next_packet(format: &mut Box<dyn FormatReader>) -> Result<Option<Packet>>
         Err(e) => Err(anyhow!(e)).context("failed reading packet"),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use symphonia::core::audio::Channels;
+    use symphonia::core::codecs::{CODEC_TYPE_AAC, CODEC_TYPE_VORBIS, CodecParameters};
+
+    fn track(id: u32, codec: symphonia::core::codecs::CodecType, language: &str) -> Track {
+        let mut params = CodecParameters::new();
+        params
+            .for_codec(codec)
+            .with_sample_rate(48_000)
+            .with_channels(Channels::FRONT_LEFT | Channels::FRONT_RIGHT);
+        Track {
+            id,
+            codec_params: params,
+            language: Some(language.to_owned()),
+        }
+    }
+
+    fn tracks() -> Vec<Track> {
+        vec![
+            track(1, CODEC_TYPE_AAC, "eng"),
+            track(2, CODEC_TYPE_VORBIS, "ger"),
+        ]
+    }
+
+    fn ids(tracks: Vec<Track>) -> Vec<u32> {
+        tracks.iter().map(|t| t.id).collect()
+    }
+
+    #[test]
+    fn select_tracks_by_index_language_and_all() -> anyhow::Result<()> {
+        assert_eq!(ids(select_tracks(&tracks(), &TrackSelection::First)?), [1]);
+        assert_eq!(
+            ids(select_tracks(&tracks(), &TrackSelection::Index(1))?),
+            [2]
+        );
+        assert_eq!(
+            ids(select_tracks(
+                &tracks(),
+                &TrackSelection::Language("GER".to_owned())
+            )?),
+            [2]
+        );
+        assert_eq!(ids(select_tracks(&tracks(), &TrackSelection::All)?), [1, 2]);
+        Ok(())
+    }
+
+    #[test]
+    fn select_tracks_lists_available_tracks_when_nothing_matches() {
+        let err = select_tracks(&tracks(), &TrackSelection::Language("fre".to_owned()))
+            .unwrap_err()
+            .to_string();
+
+        assert!(err.contains("no audio track matches 'fre'"));
+        assert!(err.contains("#0 eng, aac, 48000 Hz, 2 ch"));
+        assert!(err.contains("#1 ger, vorbis, 48000 Hz, 2 ch"));
+    }
+}
@@ -40,6 +40,10 @@ pub enum Error {
         source: Option<Box<dyn StdError + Send + Sync>>,
     },
 
+    /// The transcription was aborted via `Opts::cancellation_token`.
+    #[error("transcription cancelled")]
+    Cancelled,
+
     #[error("{message}")]
     Other {
         message: String,
@@ -72,6 +76,12 @@ impl StdError for AnyhowChainError {}
 
 impl From<anyhow::Error> for Error {
     fn from(err: anyhow::Error) -> Self {
+        // Cancellation travels through `anyhow`-based internals (e.g. the incremental
+        // transcriber); keep it distinguishable so callers can match on it.
+        if matches!(err.downcast_ref::<Error>(), Some(Error::Cancelled)) {
+            return Error::Cancelled;
+        }
+
         Error::Other {
             message: err.to_string(),
             source: Some(Box::new(AnyhowChainError {
@@ -0,0 +1,427 @@
+//! Post-processing that drops segments Whisper commonly hallucinates.
+//!
+//! On silence, music, or noise Whisper tends to emit phantom text ("Thank you for watching.")
+//! or loop on one phrase. The filter sits between the backend and the output encoder, so every
+//! backend and output format benefits from it, and counts what it removes so callers can tell
+//! how much was dropped.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::Result;
+use crate::segment_encoder::SegmentEncoder;
+use crate::segments::Segment;
+
+/// Phrases Whisper emits over silence or music, taken from common subtitle training data.
+pub const DEFAULT_HALLUCINATION_PHRASES: &[&str] = &[
+    "thank you for watching",
+    "thanks for watching",
+    "thank you for watching and please subscribe",
+    "please subscribe to my channel",
+    "subtitles by the amara.org community",
+    "like and subscribe",
+];
+
+/// Thresholds and phrase list for [`Opts::hallucination_filter`](crate::Opts).
+///
+/// A segment is dropped when any check matches. Construct with [`HallucinationFilter::default`]
+/// and adjust individual fields as needed.
+#[derive(Debug, Clone)]
+pub struct HallucinationFilter {
+    /// Drop segments whose no-speech probability is above this value.
+    pub max_no_speech_probability: f32,
+
+    /// Drop segments whose mean token probability is below this value.
+    ///
+    /// Segments without text tokens are never dropped by this check.
+    pub min_avg_token_probability: f32,
+
+    /// Length (in words) of the n-grams checked for repetition within a segment.
+    pub repetition_ngram_len: usize,
+
+    /// Drop a segment when any n-gram occurs more than this many times in it, and drop a
+    /// segment whose text repeats the previous kept segment more than this many times in a row.
+    pub max_repetitions: usize,
+
+    /// Phrases dropped when a segment's text consists of nothing else.
+    ///
+    /// Matching ignores case, punctuation, and extra whitespace.
+    pub phrases: Vec<String>,
+
+    /// Running counts of dropped segments, readable while and after the transcription runs.
+    pub stats: FilterStats,
+}
+
+impl Default for HallucinationFilter {
+    fn default() -> Self {
+        Self {
+            max_no_speech_probability: 0.8,
+            min_avg_token_probability: 0.2,
+            repetition_ngram_len: 3,
+            max_repetitions: 3,
+            phrases: DEFAULT_HALLUCINATION_PHRASES
+                .iter()
+                .map(|&phrase| phrase.to_owned())
+                .collect(),
+            stats: FilterStats::default(),
+        }
+    }
+}
+
+/// Number of segments dropped by [`HallucinationFilter`], by reason.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FilterCounts {
+    /// Dropped because the no-speech probability was too high.
+    pub no_speech: usize,
+
+    /// Dropped because the mean token probability was too low.
+    pub low_probability: usize,
+
+    /// Dropped because of repeated n-grams or consecutive duplicate segments.
+    pub repetition: usize,
+
+    /// Dropped because the text matched a known hallucination phrase.
+    pub phrase: usize,
+}
+
+impl FilterCounts {
+    /// Total number of dropped segments.
+    pub fn total(&self) -> usize {
+        self.no_speech + self.low_probability + self.repetition + self.phrase
+    }
+}
+
+/// Shared handle to the counts collected by a [`HallucinationFilter`].
+///
+/// Clone it before handing the filter to `Opts`; all clones observe the same counts.
+#[derive(Debug, Clone, Default)]
+pub struct FilterStats {
+    counts: Arc<Mutex<FilterCounts>>,
+}
+
+impl FilterStats {
+    /// Snapshot of the counts so far.
+    pub fn counts(&self) -> FilterCounts {
+        *self.lock()
+    }
+
+    fn record(&self, reason: DropReason) {
+        let mut counts = self.lock();
+        match reason {
+            DropReason::NoSpeech => counts.no_speech += 1,
+            DropReason::LowProbability => counts.low_probability += 1,
+            DropReason::Repetition => counts.repetition += 1,
+            DropReason::Phrase => counts.phrase += 1,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, FilterCounts> {
+        // Counts are plain integers, so a poisoned lock still holds usable data.
+        self.counts.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DropReason {
+    NoSpeech,
+    LowProbability,
+    Repetition,
+    Phrase,
+}
+
+/// Adapter that applies a [`HallucinationFilter`] before forwarding segments.
+///
+/// With no filter configured, segments pass through unchanged. Partial (provisional) segments
+/// are filtered with the same checks but not counted, since they are reported again once final.
+pub(crate) struct FilteringEncoder<'a> {
+    inner: &'a mut dyn SegmentEncoder,
+    filter: Option<&'a HallucinationFilter>,
+    normalized_phrases: Vec<String>,
+    previous_text: Option<String>,
+    previous_run: usize,
+}
+
+impl<'a> FilteringEncoder<'a> {
+    pub(crate) fn new(
+        inner: &'a mut dyn SegmentEncoder,
+        filter: Option<&'a HallucinationFilter>,
+    ) -> Self {
+        let normalized_phrases = filter
+            .map(|filter| filter.phrases.iter().map(|p| normalize(p)).collect())
+            .unwrap_or_default();
+
+        Self {
+            inner,
+            filter,
+            normalized_phrases,
+            previous_text: None,
+            previous_run: 0,
+        }
+    }
+
+    /// Checks that depend only on the segment itself.
+    fn content_check(&self, filter: &HallucinationFilter, seg: &Segment) -> Option<DropReason> {
+        if seg.no_speech_probability > filter.max_no_speech_probability {
+            return Some(DropReason::NoSpeech);
+        }
+
+        if avg_token_probability(seg).is_some_and(|p| p < filter.min_avg_token_probability) {
+            return Some(DropReason::LowProbability);
+        }
+
+        let text = normalize(&seg.text);
+        if !text.is_empty() && self.normalized_phrases.contains(&text) {
+            return Some(DropReason::Phrase);
+        }
+
+        if max_ngram_count(&text, filter.repetition_ngram_len) > filter.max_repetitions {
+            return Some(DropReason::Repetition);
+        }
+
+        None
+    }
+}
+
+impl SegmentEncoder for FilteringEncoder<'_> {
+    fn write_segment(&mut self, seg: &Segment) -> Result<()> {
+        let Some(filter) = self.filter else {
+            return self.inner.write_segment(seg);
+        };
+
+        if let Some(reason) = self.content_check(filter, seg) {
+            filter.stats.record(reason);
+            return Ok(());
+        }
+
+        let text = normalize(&seg.text);
+        if self.previous_text.as_deref() == Some(text.as_str()) {
+            self.previous_run += 1;
+            if self.previous_run > filter.max_repetitions {
+                filter.stats.record(DropReason::Repetition);
+                return Ok(());
+            }
+        } else {
+            self.previous_text = Some(text);
+            self.previous_run = 1;
+        }
+
+        self.inner.write_segment(seg)
+    }
+
+    fn write_partial(&mut self, segments: &[Segment]) -> Result<()> {
+        let Some(filter) = self.filter else {
+            return self.inner.write_partial(segments);
+        };
+
+        let kept: Vec<Segment> = segments
+            .iter()
+            .filter(|seg| self.content_check(filter, seg).is_none())
+            .cloned()
+            .collect();
+        self.inner.write_partial(&kept)
+    }
+
+    fn close(&mut self) -> Result<()> {
+        self.inner.close()
+    }
+}
+
+/// Lowercase, strip punctuation, and collapse whitespace so near-identical texts compare equal.
+fn normalize(text: &str) -> String {
+    text.split_whitespace()
+        .map(|word| {
+            word.chars()
+                .filter(|c| c.is_alphanumeric() || *c == '\'')
+                .flat_map(char::to_lowercase)
+                .collect::<String>()
+        })
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Mean probability of the segment's text tokens, ignoring whisper control tokens like `[_BEG_]`.
+fn avg_token_probability(seg: &Segment) -> Option<f32> {
+    let probabilities: Vec<f32> = seg
+        .tokens
+        .iter()
+        .filter(|token| token.is_text())
+        .map(|token| token.probability)
+        .collect();
+
+    if probabilities.is_empty() {
+        return None;
+    }
+    Some(probabilities.iter().sum::<f32>() / probabilities.len() as f32)
+}
+
+/// Highest occurrence count of any `n`-word sequence in `normalized_text`.
+fn max_ngram_count(normalized_text: &str, n: usize) -> usize {
+    let words: Vec<&str> = normalized_text
+        .split(' ')
+        .filter(|w| !w.is_empty())
+        .collect();
+    if n == 0 || words.len() < n {
+        return 0;
+    }
+
+    let mut counts: HashMap<&[&str], usize> = HashMap::new();
+    for ngram in words.windows(n) {
+        *counts.entry(ngram).or_default() += 1;
+    }
+    counts.into_values().max().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::token::{Token, TokenKind};
+
+    fn seg(text: &str) -> Segment {
+        Segment {
+            start_seconds: 0.0,
+            end_seconds: 1.0,
+            text: text.to_owned(),
+            ..Segment::default()
+        }
+    }
+
+    fn token(text: &str, probability: f32) -> Token {
+        Token {
+            start_seconds: 0.0,
+            end_seconds: 0.0,
+            text: text.to_owned(),
+            probability,
+            kind: TokenKind::classify(text),
+        }
+    }
+
+    #[derive(Default)]
+    struct CollectingEncoder {
+        texts: Vec<String>,
+        partials: Vec<Vec<String>>,
+    }
+
+    impl SegmentEncoder for CollectingEncoder {
+        fn write_segment(&mut self, seg: &Segment) -> Result<()> {
+            self.texts.push(seg.text.clone());
+            Ok(())
+        }
+
+        fn write_partial(&mut self, segments: &[Segment]) -> Result<()> {
+            self.partials
+                .push(segments.iter().map(|s| s.text.clone()).collect());
+            Ok(())
+        }
+
+        fn close(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn run(filter: &HallucinationFilter, segments: &[Segment]) -> Result<Vec<String>> {
+        let mut out = CollectingEncoder::default();
+        let mut enc = FilteringEncoder::new(&mut out, Some(filter));
+        for seg in segments {
+            enc.write_segment(seg)?;
+        }
+        Ok(out.texts)
+    }
+
+    #[test]
+    fn passes_everything_through_without_a_filter() -> Result<()> {
+        let mut out = CollectingEncoder::default();
+        let mut enc = FilteringEncoder::new(&mut out, None);
+        enc.write_segment(&seg(" Thank you for watching."))?;
+        assert_eq!(out.texts, vec![" Thank you for watching."]);
+        Ok(())
+    }
+
+    #[test]
+    fn drops_known_phrases_ignoring_case_and_punctuation() -> Result<()> {
+        let filter = HallucinationFilter::default();
+        let kept = run(
+            &filter,
+            &[
+                seg(" Thank you for watching!"),
+                seg(" Thank you for the update."),
+            ],
+        )?;
+
+        assert_eq!(kept, vec![" Thank you for the update."]);
+        assert_eq!(filter.stats.counts().phrase, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn drops_high_no_speech_and_low_probability_segments() -> Result<()> {
+        let filter = HallucinationFilter::default();
+
+        let mut silent = seg(" you");
+        silent.no_speech_probability = 0.95;
+
+        let mut unsure = seg(" mumble");
+        unsure.tokens = vec![
+            token("[_BEG_]", 0.99),
+            token(" mum", 0.1),
+            token("ble", 0.05),
+        ];
+
+        let mut sure = seg(" hello");
+        sure.tokens = vec![token(" hello", 0.9)];
+
+        let kept = run(&filter, &[silent, unsure, sure])?;
+
+        assert_eq!(kept, vec![" hello"]);
+        let counts = filter.stats.counts();
+        assert_eq!(counts.no_speech, 1);
+        assert_eq!(counts.low_probability, 1);
+        assert_eq!(counts.total(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn drops_segments_with_repeated_ngrams() -> Result<()> {
+        let filter = HallucinationFilter::default();
+        let looped = seg(" I am here. I am here. I am here. I am here. I am here.");
+
+        let kept = run(&filter, &[looped, seg(" I am here, I am here.")])?;
+
+        assert_eq!(kept, vec![" I am here, I am here."]);
+        assert_eq!(filter.stats.counts().repetition, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn drops_long_runs_of_identical_segments() -> Result<()> {
+        let filter = HallucinationFilter::default();
+        let segments = vec![seg(" Okay."); 6];
+
+        let kept = run(&filter, &segments)?;
+
+        assert_eq!(kept.len(), filter.max_repetitions);
+        assert_eq!(filter.stats.counts().repetition, 3);
+        Ok(())
+    }
+
+    #[test]
+    fn filters_partials_without_counting_them() -> Result<()> {
+        let filter = HallucinationFilter::default();
+        let mut out = CollectingEncoder::default();
+        let mut enc = FilteringEncoder::new(&mut out, Some(&filter));
+
+        enc.write_partial(&[seg(" hello"), seg(" Thanks for watching.")])?;
+
+        assert_eq!(out.partials, vec![vec![" hello".to_owned()]]);
+        assert_eq!(filter.stats.counts().total(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn stats_are_shared_between_clones() {
+        let filter = HallucinationFilter::default();
+        let stats = filter.stats.clone();
+        filter.stats.record(DropReason::Phrase);
+        assert_eq!(stats.counts().phrase, 1);
+    }
+}
@@ -121,9 +121,7 @@ mod tests {
             start_seconds: start,
             end_seconds: end,
             text: text.to_string(),
-            tokens: Vec::new(),
-            language_code: "en".to_string(),
-            next_speaker_turn: false,
+            ..Segment::default()
         }
     }
 
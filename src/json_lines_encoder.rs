@@ -0,0 +1,260 @@
+use std::io::Write;
+
+use serde::Serialize;
+
+use crate::Result;
+use crate::segment_encoder::SegmentEncoder;
+use crate::segments::Segment;
+
+/// Whether a JSON Lines record is committed or may still change.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Status {
+    Final,
+    Partial,
+}
+
+/// One line of output: the segment fields plus a `status` marker.
+#[derive(Serialize)]
+struct Line<'a> {
+    status: Status,
+    #[serde(flatten)]
+    segment: &'a Segment,
+}
+
+/// A `SegmentEncoder` that writes one JSON object per line (JSON Lines / NDJSON).
+///
+/// Design:
+/// - Every line is self-contained, so consumers can act on each record as soon as it arrives
+///   (no enclosing array to wait for).
+/// - Committed segments are written with `"status": "final"`.
+/// - The provisional tail from `write_partial` is merged into a single `"status": "partial"`
+///   line; each partial line replaces the previous one until the next final line lands.
+///
+/// Example output:
+/// ```json
+/// {"status":"partial","start_seconds":0.0,"end_seconds":1.2,"text":" and so my", ...}
+/// {"status":"final","start_seconds":0.0,"end_seconds":2.5,"text":" and so my fellow", ...}
+/// ```
+pub struct JsonLinesEncoder<W: Write> {
+    /// The underlying writer receiving JSON Lines output.
+    w: W,
+
+    /// Whether the encoder has been closed.
+    /// Once closed, no further writes are allowed.
+    closed: bool,
+}
+
+impl<W: Write> JsonLinesEncoder<W> {
+    /// Create a new JSON Lines encoder that writes to the given writer.
+    pub fn new(w: W) -> Self {
+        Self { w, closed: false }
+    }
+
+    fn write_line(&mut self, status: Status, segment: &Segment) -> Result<()> {
+        if self.closed {
+            return Err(crate::Error::invalid_input(
+                "cannot write segment: encoder is already closed",
+            ));
+        }
+
+        serde_json::to_writer(&mut self.w, &Line { status, segment })?;
+        self.w.write_all(b"\n")?;
+
+        // Flush so live consumers (captions, sockets) see each line promptly.
+        self.w.flush()?;
+        Ok(())
+    }
+}
+
+impl<W: Write> SegmentEncoder for JsonLinesEncoder<W> {
+    fn write_segment(&mut self, seg: &Segment) -> Result<()> {
+        self.write_line(Status::Final, seg)
+    }
+
+    fn write_partial(&mut self, segments: &[Segment]) -> Result<()> {
+        match merge_segments(segments) {
+            Some(merged) => self.write_line(Status::Partial, &merged),
+            None => Ok(()),
+        }
+    }
+
+    /// Flush the underlying writer. Idempotent.
+    fn close(&mut self) -> Result<()> {
+        if self.closed {
+            return Ok(());
+        }
+        self.w.flush()?;
+        self.closed = true;
+        Ok(())
+    }
+}
+
+/// Collapse a provisional tail into one segment spanning all of it.
+///
+/// Scores are combined as if the tail were one segment: `avg_logprob` is weighted by text-token
+/// count, and `confidence` is derived from the merged scores like any other segment's.
+fn merge_segments(segments: &[Segment]) -> Option<Segment> {
+    let (first, last) = (segments.first()?, segments.last()?);
+    let avg_logprob = weighted_avg_logprob(segments);
+    // The merged line is only as trustworthy as its least speech-like piece.
+    let no_speech_probability = segments
+        .iter()
+        .map(|s| s.no_speech_probability)
+        .fold(0.0, f32::max);
+    let has_text = segments.iter().any(|s| !s.text.trim().is_empty());
+    Some(Segment {
+        start_seconds: first.start_seconds,
+        end_seconds: last.end_seconds,
+        text: segments.iter().map(|s| s.text.as_str()).collect(),
+        tokens: segments.iter().flat_map(|s| s.tokens.clone()).collect(),
+        words: segments.iter().flat_map(|s| s.words.clone()).collect(),
+        language_code: first.language_code.clone(),
+        next_speaker_turn: last.next_speaker_turn,
+        avg_logprob,
+        no_speech_probability,
+        confidence: Segment::confidence_from(avg_logprob, no_speech_probability, has_text),
+        from_fallback: segments.iter().any(|s| s.from_fallback),
+        channel: first.channel,
+    })
+}
+
+/// Mean of the pieces' `avg_logprob`, weighted by their text-token counts.
+///
+/// Pieces without tokens (e.g. from backends that do not report them) count equally when no
+/// piece has any.
+fn weighted_avg_logprob(segments: &[Segment]) -> f32 {
+    let weight = |s: &Segment| s.tokens.iter().filter(|t| t.is_text()).count();
+    let total: usize = segments.iter().map(weight).sum();
+    if total == 0 {
+        return segments.iter().map(|s| s.avg_logprob).sum::<f32>() / segments.len() as f32;
+    }
+    segments
+        .iter()
+        .map(|s| s.avg_logprob * weight(s) as f32)
+        .sum::<f32>()
+        / total as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seg(start: f32, end: f32, text: &str) -> Segment {
+        Segment {
+            start_seconds: start,
+            end_seconds: end,
+            text: text.to_string(),
+            ..Segment::default()
+        }
+    }
+
+    fn lines(out: &[u8]) -> anyhow::Result<Vec<serde_json::Value>> {
+        std::str::from_utf8(out)?
+            .lines()
+            .map(|line| Ok(serde_json::from_str(line)?))
+            .collect()
+    }
+
+    #[test]
+    fn json_lines_writes_one_final_object_per_line() -> anyhow::Result<()> {
+        let mut out = Vec::new();
+        let mut enc = JsonLinesEncoder::new(&mut out);
+
+        enc.write_segment(&seg(0.0, 1.0, "hello"))?;
+        enc.write_segment(&seg(1.0, 2.5, "world"))?;
+        enc.close()?;
+
+        let lines = lines(&out)?;
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["status"], "final");
+        assert_eq!(lines[0]["text"], "hello");
+        assert_eq!(lines[1]["text"], "world");
+        Ok(())
+    }
+
+    #[test]
+    fn json_lines_merges_partial_tail_into_one_line() -> anyhow::Result<()> {
+        let mut out = Vec::new();
+        let mut enc = JsonLinesEncoder::new(&mut out);
+
+        enc.write_partial(&[seg(1.0, 2.0, " and so"), seg(2.0, 3.0, " my")])?;
+        enc.write_partial(&[])?;
+        enc.close()?;
+
+        let lines = lines(&out)?;
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0]["status"], "partial");
+        assert_eq!(lines[0]["text"], " and so my");
+        assert_eq!(lines[0]["start_seconds"], 1.0);
+        assert_eq!(lines[0]["end_seconds"], 3.0);
+        Ok(())
+    }
+
+    #[test]
+    fn json_lines_partial_keeps_the_highest_no_speech_probability() -> anyhow::Result<()> {
+        let mut out = Vec::new();
+        let mut enc = JsonLinesEncoder::new(&mut out);
+
+        let quiet = Segment {
+            no_speech_probability: 0.75,
+            ..seg(2.0, 3.0, " um")
+        };
+        enc.write_partial(&[seg(1.0, 2.0, " and so"), quiet])?;
+        enc.close()?;
+
+        let lines = lines(&out)?;
+        assert_eq!(lines[0]["no_speech_probability"], 0.75);
+        Ok(())
+    }
+
+    #[test]
+    fn merged_scores_weight_by_tokens_and_keep_confidence_consistent() {
+        let token = |text: &str| crate::Token {
+            start_seconds: 0.0,
+            end_seconds: 0.0,
+            text: text.to_owned(),
+            probability: 0.5,
+            kind: crate::TokenKind::Text,
+        };
+        let short = Segment {
+            tokens: vec![token(" so")],
+            avg_logprob: -1.0,
+            ..seg(1.0, 2.0, " so")
+        };
+        let long = Segment {
+            tokens: vec![token(" my"), token(" fel"), token("low")],
+            avg_logprob: 0.0,
+            no_speech_probability: 0.5,
+            ..seg(2.0, 3.0, " my fellow")
+        };
+
+        let merged = merge_segments(&[short, long]).expect("merged");
+        assert_eq!(merged.avg_logprob, -0.25);
+        assert_eq!(merged.no_speech_probability, 0.5);
+        assert_eq!(
+            merged.confidence,
+            Segment::confidence_from(-0.25, 0.5, true)
+        );
+    }
+
+    #[test]
+    fn json_lines_close_without_segments_emits_nothing() -> anyhow::Result<()> {
+        let mut out = Vec::new();
+        let mut enc = JsonLinesEncoder::new(&mut out);
+        enc.close()?;
+        enc.close()?;
+        assert!(out.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn json_lines_write_after_close_errors() -> anyhow::Result<()> {
+        let mut out = Vec::new();
+        let mut enc = JsonLinesEncoder::new(&mut out);
+        enc.close()?;
+        let err = enc.write_segment(&seg(0.0, 1.0, "nope")).unwrap_err();
+        assert!(err.to_string().contains("already closed"));
+        Ok(())
+    }
+}
@@ -9,7 +9,7 @@
 //! - Media demuxing and audio decoding (via Symphonia)
 //! - Audio normalization and resampling (mono, 16 kHz)
 //! - Backend inference (built-in Whisper backend available)
-//! - Pluggable output encoders (JSON, VTT, etc.)
+//! - Pluggable output encoders (JSON, JSON Lines, VTT, etc.)
 //!
 //! The library emphasizes:
 //! - Explicit control flow
@@ -23,11 +23,21 @@
 // High-level API
 // ─────────────────────────────────────────────────────────────────────────────
 
+#[cfg(feature = "tokio")]
+mod async_api;
 mod backend;
 mod backends;
+mod cancel;
+mod capabilities;
+mod commit_policy;
 mod error;
+mod hallucination_filter;
 mod opts;
+mod probe;
+mod progress;
 mod scribble;
+mod track_selection;
+mod transcription_mode;
 mod vad;
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -45,6 +55,7 @@ mod audio_pipeline;
 mod decode;
 mod decoder;
 mod demux;
+mod raw_audio;
 #[cfg(test)]
 mod wav;
 
@@ -53,6 +64,7 @@ mod wav;
 // ─────────────────────────────────────────────────────────────────────────────
 
 mod json_array_encoder;
+mod json_lines_encoder;
 mod output_type;
 mod segment_encoder;
 mod vtt_encoder;
@@ -66,12 +78,33 @@ mod logging;
 /// Internal adapters used to keep the high-level transcription loop linear and explicit.
 pub(crate) mod samples_rx;
 
+#[cfg(feature = "tokio")]
+pub use crate::async_api::SegmentStream;
 pub use crate::backend::{Backend, BackendStream};
+pub use crate::backends::fallback::{FallbackBackend, FallbackPolicy, FallbackStream};
+#[cfg(feature = "openai")]
+pub use crate::backends::openai::{OpenAiBackend, OpenAiStream};
+#[cfg(feature = "remote")]
+pub use crate::backends::remote::{RemoteBackend, RemoteStream};
+#[cfg(feature = "testing")]
+pub use crate::backends::scripted::{ScriptedBackend, ScriptedStream};
 pub use crate::backends::whisper::WhisperBackend;
+pub use crate::cancel::CancellationToken;
+pub use crate::capabilities::Capabilities;
+pub use crate::commit_policy::CommitPolicy;
 pub use crate::error::{Error, Result};
+pub use crate::hallucination_filter::{
+    DEFAULT_HALLUCINATION_PHRASES, FilterCounts, FilterStats, HallucinationFilter,
+};
 pub use crate::logging::init as init_logging;
 pub use crate::opts::Opts;
 pub use crate::output_type::OutputType;
+pub use crate::probe::{MediaInfo, TrackInfo, probe, probe_seekable};
+pub use crate::progress::{Progress, ProgressListener};
+pub use crate::raw_audio::{RawAudioFormat, RawSampleEncoding};
 pub use crate::scribble::Scribble;
 pub use crate::segment_encoder::SegmentEncoder;
 pub use crate::segments::Segment;
+pub use crate::token::{Token, TokenKind, Word};
+pub use crate::track_selection::TrackSelection;
+pub use crate::transcription_mode::TranscriptionMode;
@@ -1,4 +1,13 @@
+use std::sync::Arc;
+
+use crate::cancel::CancellationToken;
+use crate::commit_policy::CommitPolicy;
+use crate::hallucination_filter::HallucinationFilter;
 use crate::output_type::OutputType;
+use crate::progress::ProgressListener;
+use crate::raw_audio::RawAudioFormat;
+use crate::track_selection::TrackSelection;
+use crate::transcription_mode::TranscriptionMode;
 
 /// Options that control how a transcription is performed.
 ///
@@ -39,9 +48,157 @@ pub struct Opts {
     /// The desired output format for transcription segments.
     pub output_type: OutputType,
 
+    /// Whether to stream audio through the backend or buffer it first. See
+    /// [`TranscriptionMode`].
+    pub transcription_mode: TranscriptionMode,
+
     /// Minimum buffered audio duration (seconds) before running Whisper in incremental mode.
     ///
     /// This only affects the streaming/incremental path (when VAD is disabled). Larger windows
     /// increase latency but can improve segmentation stability.
     pub incremental_min_window_seconds: usize,
+
+    /// Maximum buffered audio duration (seconds) in incremental mode before forcing progress.
+    ///
+    /// If the commit policy keeps holding segments back, the buffer grows until this cap and is
+    /// then flushed in full to bound memory and latency. `30` matches Whisper's native window.
+    /// Values below `incremental_min_window_seconds` are raised to it.
+    pub incremental_max_buffer_seconds: usize,
+
+    /// How the incremental path decides which segments are final. See [`CommitPolicy`].
+    pub incremental_commit_policy: CommitPolicy,
+
+    /// Whether the incremental path reports provisional (not yet final) segments.
+    ///
+    /// When enabled, each streaming inference pass hands its uncommitted tail to
+    /// `SegmentEncoder::write_partial`. Encoders without interim support ignore it.
+    pub enable_partial_results: bool,
+
+    /// Whether to emit one segment per word instead of one segment per phrase.
+    ///
+    /// Useful for search indexing and clip cutting where word boundaries matter more than
+    /// readable cues. Applies to both the full-buffer and incremental paths, and to every
+    /// output format (each word becomes its own JSON element or VTT cue).
+    pub enable_word_level_segments: bool,
+
+    /// Whether to keep timestamp and control tokens (e.g. `[_TT_50]`, `[_BEG_]`) in
+    /// `Segment::tokens`.
+    ///
+    /// Off by default so consumers only see transcribed text; turn it on to inspect the raw
+    /// decoder output.
+    pub enable_non_text_tokens: bool,
+
+    /// Container format hint for the input, as a file extension (e.g. `"mp4"`, `"mkv"`).
+    ///
+    /// Forwarded to the decoder's container probe as a hint; the container is still detected
+    /// from the content. `None` probes without a hint.
+    pub input_format: Option<String>,
+
+    /// Layout of headerless PCM input (e.g. `s16le:8000:1` from a telephony gateway).
+    ///
+    /// When set, the input is read as raw samples in this format instead of being probed as a
+    /// container; `input_format` is ignored. See [`RawAudioFormat`].
+    pub raw_input_format: Option<RawAudioFormat>,
+
+    /// Which audio track(s) of a multi-track container to transcribe. See [`TrackSelection`].
+    ///
+    /// `TrackSelection::First` matches the behavior for single-track inputs.
+    pub audio_track: TrackSelection,
+
+    /// Whether to transcribe each audio channel separately instead of downmixing to mono.
+    ///
+    /// Meant for recordings with one speaker per channel (e.g. call-center stereo: agent left,
+    /// customer right). Segments from all channels are merged by start time and labeled with
+    /// [`crate::Segment::channel`]. The whole input is read before the first channel is
+    /// transcribed, so this is not suited to live streams. `max_wall_clock_seconds` is shared
+    /// evenly between the channels.
+    pub enable_per_channel_transcription: bool,
+
+    /// Offset (seconds into the input) where transcription starts.
+    ///
+    /// Seekable inputs are seeked there; for other inputs, the audio before it is decoded and
+    /// skipped. Output timestamps stay relative to the start of the original media. When `None`,
+    /// transcription starts at the beginning.
+    pub start_seconds: Option<f32>,
+
+    /// Offset (seconds into the input) where transcription ends; decoding stops there.
+    ///
+    /// When `None`, transcription runs to the end of the input.
+    pub end_seconds: Option<f32>,
+
+    /// Stop after this many seconds of input audio (counted from `start_seconds`), as if the
+    /// input ended there.
+    ///
+    /// Decoding stops early, so the rest of the input is never read. Segments for the audio up to
+    /// the limit are still emitted. When `None`, the whole input is transcribed.
+    pub max_audio_seconds: Option<usize>,
+
+    /// Stop reading input once the transcription has run for this many seconds of wall-clock
+    /// time.
+    ///
+    /// Audio already read is still transcribed and its segments emitted, so the call can run
+    /// somewhat past the limit. Useful for bounding work on live inputs. When `None`, there is no
+    /// time limit.
+    pub max_wall_clock_seconds: Option<usize>,
+
+    /// Optional filter that drops likely-hallucinated segments before they reach the encoder.
+    ///
+    /// Removes phantom text over silence or music and repetition loops; read the number of
+    /// dropped segments from its `stats` handle. When `None`, every segment is emitted.
+    pub hallucination_filter: Option<HallucinationFilter>,
+
+    /// Optional handle used to abort the transcription while it is running.
+    ///
+    /// When the token is cancelled, transcription stops at the next check point and returns
+    /// [`crate::Error::Cancelled`]. When `None`, the transcription runs until the input ends.
+    pub cancellation_token: Option<CancellationToken>,
+
+    /// Optional listener notified as audio is decoded and transcribed.
+    ///
+    /// Reports decoded and transcribed seconds, the total duration when the container knows it,
+    /// and the backend's own per-pass progress. When `None`, no progress is tracked.
+    pub progress_listener: Option<Arc<dyn ProgressListener>>,
+}
+
+/// Streaming transcription to JSON with every optional feature off, no limits, and the
+/// incremental defaults the CLI uses (1 s minimum window, 30 s buffer cap, `AllButLast`).
+///
+/// Set only the fields you need and fill in the rest with `..Opts::default()`.
+impl Default for Opts {
+    fn default() -> Self {
+        Self {
+            model_key: None,
+            enable_translate_to_english: false,
+            enable_voice_activity_detection: false,
+            language: None,
+            output_type: OutputType::Json,
+            transcription_mode: TranscriptionMode::Streaming,
+            incremental_min_window_seconds: 1,
+            incremental_max_buffer_seconds: 30,
+            incremental_commit_policy: CommitPolicy::AllButLast,
+            enable_partial_results: false,
+            enable_word_level_segments: false,
+            enable_non_text_tokens: false,
+            input_format: None,
+            raw_input_format: None,
+            audio_track: TrackSelection::First,
+            enable_per_channel_transcription: false,
+            start_seconds: None,
+            end_seconds: None,
+            max_audio_seconds: None,
+            max_wall_clock_seconds: None,
+            hallucination_filter: None,
+            cancellation_token: None,
+            progress_listener: None,
+        }
+    }
+}
+
+impl Opts {
+    /// Whether the caller has requested cancellation via `cancellation_token`.
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.cancellation_token
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+    }
 }
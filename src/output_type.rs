@@ -17,4 +17,11 @@ pub enum OutputType {
 
     /// Output segments in WebVTT subtitle format.
     Vtt,
+
+    /// Output one JSON object per line, each marked `"final"` or `"partial"`.
+    ///
+    /// Suited to live captioning: partial lines carry the provisional tail when
+    /// `Opts::enable_partial_results` is set.
+    #[cfg_attr(feature = "cli", value(name = "jsonl"))]
+    JsonLines,
 }
@@ -0,0 +1,259 @@
+//! Inspect media (duration, tracks, tags) without transcribing it.
+//!
+//! Probing only reads as far as the container headers, so it is cheap enough to run before
+//! queuing a job or accepting an upload.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::{Read, Seek};
+
+use serde::Serialize;
+use symphonia::core::meta::{MetadataRevision, Value};
+
+use crate::Result;
+use crate::decoder::DecodeInput;
+use crate::demux::{audio_tracks, codec_name, probe_source, track_duration_seconds};
+use crate::track_selection::TrackSelection;
+
+/// Container and audio track metadata, as returned by [`probe`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MediaInfo {
+    /// Duration of the longest audio track in seconds, when the container reports it.
+    pub duration_seconds: Option<f64>,
+
+    /// Audio tracks Scribble can decode, in container order.
+    ///
+    /// Empty when the container holds no such track (e.g. a video without audio).
+    pub tracks: Vec<TrackInfo>,
+
+    /// Metadata tags (title, artist, ...) by name.
+    ///
+    /// Well-known tags use Symphonia's standard names (e.g. `TrackTitle`); others keep the
+    /// container's own key. Binary tags such as cover art are left out.
+    pub tags: BTreeMap<String, String>,
+}
+
+/// One decodable audio track of a probed input.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TrackInfo {
+    /// Position among the audio tracks, as used by [`crate::TrackSelection::Index`].
+    pub index: usize,
+
+    /// Codec short name, e.g. `aac` or `pcm_s16le`.
+    pub codec: String,
+
+    /// Language tag, usually a three-letter ISO 639-2 code (e.g. `eng`).
+    pub language: Option<String>,
+
+    /// Sample rate in Hz.
+    pub sample_rate: u32,
+
+    /// Channel count, when the container reports it.
+    pub channels: Option<usize>,
+
+    /// Track duration in seconds, when the container reports it.
+    pub duration_seconds: Option<f64>,
+}
+
+impl MediaInfo {
+    /// The tracks `selection` picks, as transcription would select them.
+    ///
+    /// Fails with [`crate::Error::InvalidInput`] listing the available tracks when nothing
+    /// matches, so a request can be rejected before transcription starts.
+    pub fn select_tracks(&self, selection: &TrackSelection) -> Result<Vec<&TrackInfo>> {
+        let selected = match selection {
+            TrackSelection::First => self.tracks.first(),
+            TrackSelection::Index(index) => self.tracks.get(*index),
+            TrackSelection::Language(language) => self.tracks.iter().find(|t| {
+                t.language
+                    .as_deref()
+                    .is_some_and(|l| l.eq_ignore_ascii_case(language))
+            }),
+            TrackSelection::All if !self.tracks.is_empty() => {
+                return Ok(self.tracks.iter().collect());
+            }
+            TrackSelection::All => None,
+        };
+
+        selected.map(|track| vec![track]).ok_or_else(|| {
+            let available: Vec<String> = self.tracks.iter().map(ToString::to_string).collect();
+            crate::Error::invalid_input(format!(
+                "no audio track matches '{selection}'; available audio tracks: {}",
+                available.join("; ")
+            ))
+        })
+    }
+}
+
+/// Formats as a one-line summary, e.g. `#1 eng, aac, 48000 Hz, 2 ch`.
+impl fmt::Display for TrackInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "#{} {}, {}",
+            self.index,
+            self.language.as_deref().unwrap_or("und"),
+            self.codec
+        )?;
+        if self.sample_rate > 0 {
+            write!(f, ", {} Hz", self.sample_rate)?;
+        }
+        if let Some(channels) = self.channels {
+            write!(f, ", {channels} ch")?;
+        }
+        Ok(())
+    }
+}
+
+/// Probe a byte stream for its container and audio track metadata.
+///
+/// `hint_extension` is an optional container hint as a file extension (e.g. `"mp4"`), the same
+/// as `Opts::input_format`; the container is still detected from the content.
+///
+/// Some containers (notably MP4/MOV files with a trailing `moov` box) can only be probed from a
+/// seekable input; use [`probe_seekable`] for those.
+///
+/// Fails with [`crate::Error::Decode`] when the container is not recognized.
+pub fn probe<R>(reader: R, hint_extension: Option<&str>) -> Result<MediaInfo>
+where
+    R: Read + Send + 'static,
+{
+    probe_input(DecodeInput::Stream(Box::new(reader)), hint_extension)
+}
+
+/// Probe a seekable input (typically a `File`) for its container and audio track metadata.
+///
+/// Same as [`probe`], but handles containers that keep their metadata at the end.
+pub fn probe_seekable<R>(reader: R, hint_extension: Option<&str>) -> Result<MediaInfo>
+where
+    R: Read + Seek + Send + 'static,
+{
+    probe_input(DecodeInput::Seekable(Box::new(reader)), hint_extension)
+}
+
+fn probe_input(input: DecodeInput, hint_extension: Option<&str>) -> Result<MediaInfo> {
+    media_info(input, hint_extension).map_err(|err| crate::Error::Decode {
+        message: format!("{err:#}"),
+        source: None,
+    })
+}
+
+fn media_info(input: DecodeInput, hint_extension: Option<&str>) -> anyhow::Result<MediaInfo> {
+    let mut probed = probe_source(input.into_media_source()?, hint_extension)?;
+
+    let tracks: Vec<TrackInfo> = audio_tracks(probed.format.as_ref())
+        .iter()
+        .enumerate()
+        .map(|(index, track)| TrackInfo {
+            index,
+            codec: codec_name(track).to_owned(),
+            language: track.language.clone(),
+            sample_rate: track.codec_params.sample_rate.unwrap_or_default(),
+            channels: track.codec_params.channels.map(|channels| channels.count()),
+            duration_seconds: track_duration_seconds(track),
+        })
+        .collect();
+
+    // Tags found ahead of the container (e.g. ID3) come first; the container's own tags win.
+    let mut tags = BTreeMap::new();
+    if let Some(revision) = probed.metadata.get().as_ref().and_then(|m| m.current()) {
+        add_tags(&mut tags, revision);
+    }
+    if let Some(revision) = probed.format.metadata().current() {
+        add_tags(&mut tags, revision);
+    }
+
+    Ok(MediaInfo {
+        duration_seconds: tracks
+            .iter()
+            .filter_map(|track| track.duration_seconds)
+            .reduce(f64::max),
+        tracks,
+        tags,
+    })
+}
+
+fn add_tags(tags: &mut BTreeMap<String, String>, revision: &MetadataRevision) {
+    for tag in revision.tags() {
+        if matches!(tag.value, Value::Binary(_)) {
+            continue;
+        }
+        let key = tag
+            .std_key
+            .map_or_else(|| tag.key.clone(), |key| format!("{key:?}"));
+        // Some containers (e.g. RIFF INFO chunks) store NUL-terminated strings.
+        let value = tag.value.to_string();
+        tags.insert(key, value.trim_end_matches('\0').to_owned());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn probe_reports_tracks_and_duration() -> anyhow::Result<()> {
+        let info = probe_seekable(
+            std::fs::File::open("tests/fixtures/tone-moov-at-end.mp4")?,
+            Some("mp4"),
+        )?;
+
+        assert_eq!(
+            info.tracks,
+            vec![TrackInfo {
+                index: 0,
+                codec: "pcm_s16le".to_owned(),
+                language: None,
+                sample_rate: 16_000,
+                channels: Some(1),
+                duration_seconds: Some(2.0),
+            }]
+        );
+        assert_eq!(info.duration_seconds, Some(2.0));
+        Ok(())
+    }
+
+    #[test]
+    fn select_tracks_lists_available_tracks_when_nothing_matches() {
+        let track = |index, language: &str| TrackInfo {
+            index,
+            codec: "aac".to_owned(),
+            language: Some(language.to_owned()),
+            sample_rate: 48_000,
+            channels: Some(2),
+            duration_seconds: None,
+        };
+        let info = MediaInfo {
+            duration_seconds: None,
+            tracks: vec![track(0, "eng"), track(1, "ger")],
+            tags: BTreeMap::new(),
+        };
+
+        let selected = info
+            .select_tracks(&TrackSelection::Language("GER".to_owned()))
+            .expect("ger track");
+        assert_eq!(selected, vec![&info.tracks[1]]);
+        assert_eq!(
+            info.select_tracks(&TrackSelection::All)
+                .map(|t| t.len())
+                .ok(),
+            Some(2)
+        );
+
+        let err = info.select_tracks(&TrackSelection::Index(2)).unwrap_err();
+        assert!(matches!(err, crate::Error::InvalidInput { .. }));
+        assert!(
+            err.to_string().ends_with(
+                "available audio tracks: #0 eng, aac, 48000 Hz, 2 ch; #1 ger, aac, 48000 Hz, 2 ch"
+            ),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn probe_rejects_unrecognized_media() {
+        let err = probe(std::io::Cursor::new(b"not a media file".to_vec()), None).unwrap_err();
+        assert!(matches!(err, crate::Error::Decode { .. }));
+        assert!(err.to_string().contains("failed to probe media stream"));
+    }
+}
@@ -0,0 +1,249 @@
+//! Progress reporting for long-running transcriptions.
+//!
+//! Progress is reported from two places:
+//! - the high-level pipeline, as [`Progress`] snapshots (decoded vs transcribed audio)
+//! - the backend, as per-inference-pass percentages (e.g. whisper.cpp's progress callback)
+//!
+//! Listeners are called synchronously from the decode thread and the transcription thread, so
+//! implementations should return quickly (update a UI handle, store a value, etc.).
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use crate::Result;
+use crate::segment_encoder::SegmentEncoder;
+use crate::segments::Segment;
+
+/// A snapshot of transcription progress, in seconds of source audio.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Progress {
+    /// Audio decoded so far.
+    pub decoded_seconds: f64,
+
+    /// Audio the backend has finished transcribing (end of the latest emitted segment).
+    ///
+    /// Reaches `decoded_seconds` once the transcription completes.
+    pub transcribed_seconds: f64,
+
+    /// Total duration of the selected audio track, when the container reports it.
+    ///
+    /// `None` for live streams and containers without duration metadata.
+    pub total_seconds: Option<f64>,
+}
+
+/// Receives progress updates during a transcription.
+///
+/// Set via `Opts::progress_listener`. Implemented for closures taking `&Progress`, so simple
+/// callers do not need a dedicated type.
+pub trait ProgressListener: Send + Sync {
+    /// Called whenever the pipeline-level progress snapshot changes.
+    fn on_progress(&self, progress: &Progress);
+
+    /// Called with the backend's progress (0–100) through its current inference pass.
+    ///
+    /// Each pass covers one buffered window, so the percentage restarts for every window in
+    /// streaming mode. Defaults to a no-op.
+    fn on_backend_progress(&self, _percent: i32) {}
+}
+
+impl<F> ProgressListener for F
+where
+    F: Fn(&Progress) + Send + Sync,
+{
+    fn on_progress(&self, progress: &Progress) {
+        self(progress)
+    }
+}
+
+/// Listeners are opaque; this keeps `Opts` debuggable without constraining implementors.
+impl fmt::Debug for dyn ProgressListener {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ProgressListener")
+    }
+}
+
+/// Shared progress state for a single transcription.
+///
+/// Updated from both the decode thread and the transcription thread; every update notifies the
+/// listener with a fresh snapshot.
+pub(crate) struct ProgressReporter {
+    listener: Arc<dyn ProgressListener>,
+    state: Mutex<ReporterState>,
+}
+
+struct ReporterState {
+    progress: Progress,
+    /// Passes over the same audio reported as one run (one per channel when transcribing
+    /// channels separately).
+    passes: usize,
+    /// Audio decoded by earlier passes; the current pass's positions count from here.
+    pass_offset_seconds: f64,
+}
+
+impl ProgressReporter {
+    pub(crate) fn new(listener: Arc<dyn ProgressListener>) -> Self {
+        Self {
+            listener,
+            state: Mutex::new(ReporterState {
+                progress: Progress::default(),
+                passes: 1,
+                pass_offset_seconds: 0.0,
+            }),
+        }
+    }
+
+    /// Report `passes` passes over the same audio as one run: the total covers every pass and
+    /// each pass picks up where the previous one ended. Call before the first pass.
+    pub(crate) fn set_passes(&self, passes: usize) {
+        self.lock().passes = passes.max(1);
+    }
+
+    /// Begin the next pass over the audio.
+    pub(crate) fn start_pass(&self) {
+        self.update(|s| {
+            s.pass_offset_seconds = s.progress.decoded_seconds;
+            s.progress.transcribed_seconds = s.pass_offset_seconds;
+        });
+    }
+
+    pub(crate) fn set_total_seconds(&self, total_seconds: f64) {
+        self.update(|s| s.progress.total_seconds = Some(total_seconds * s.passes as f64));
+    }
+
+    pub(crate) fn add_decoded_seconds(&self, seconds: f64) {
+        self.update(|s| s.progress.decoded_seconds += seconds);
+    }
+
+    /// Record that audio up to `seconds` has been transcribed. Never moves backwards.
+    pub(crate) fn set_transcribed_seconds(&self, seconds: f64) {
+        self.update(|s| {
+            let seconds = s.pass_offset_seconds + seconds;
+            s.progress.transcribed_seconds = s.progress.transcribed_seconds.max(seconds);
+        });
+    }
+
+    /// Mark all decoded audio as transcribed (end of a successful run).
+    pub(crate) fn finish(&self) {
+        self.update(|s| s.progress.transcribed_seconds = s.progress.decoded_seconds);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ReporterState> {
+        // A poisoned lock only means another listener call panicked; progress is best-effort,
+        // so keep reporting from the last known state.
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn update(&self, f: impl FnOnce(&mut ReporterState)) {
+        let snapshot = {
+            let mut state = self.lock();
+            f(&mut state);
+            state.progress
+        };
+        self.listener.on_progress(&snapshot);
+    }
+}
+
+/// A `SegmentEncoder` adapter that reports transcribed progress as segments are written.
+///
+/// Keeps progress tracking backend-agnostic: any backend that emits segments advances
+/// `transcribed_seconds` without knowing about progress reporting.
+pub(crate) struct ProgressEncoder<'a> {
+    inner: &'a mut dyn SegmentEncoder,
+    reporter: Option<Arc<ProgressReporter>>,
+}
+
+impl<'a> ProgressEncoder<'a> {
+    pub(crate) fn new(
+        inner: &'a mut dyn SegmentEncoder,
+        reporter: Option<Arc<ProgressReporter>>,
+    ) -> Self {
+        Self { inner, reporter }
+    }
+}
+
+impl SegmentEncoder for ProgressEncoder<'_> {
+    fn write_segment(&mut self, seg: &Segment) -> Result<()> {
+        self.inner.write_segment(seg)?;
+        if let Some(reporter) = &self.reporter {
+            reporter.set_transcribed_seconds(seg.end_seconds as f64);
+        }
+        Ok(())
+    }
+
+    fn write_partial(&mut self, segments: &[Segment]) -> Result<()> {
+        self.inner.write_partial(segments)
+    }
+
+    fn close(&mut self) -> Result<()> {
+        self.inner.close()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recording_reporter() -> (Arc<ProgressReporter>, Arc<Mutex<Vec<Progress>>>) {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        let listener = move |p: &Progress| sink.lock().expect("lock").push(*p);
+        (Arc::new(ProgressReporter::new(Arc::new(listener))), seen)
+    }
+
+    #[test]
+    fn reporter_accumulates_decoded_and_reports_snapshots() {
+        let (reporter, seen) = recording_reporter();
+        reporter.set_total_seconds(10.0);
+        reporter.add_decoded_seconds(1.5);
+        reporter.add_decoded_seconds(0.5);
+
+        let seen = seen.lock().expect("lock");
+        assert_eq!(seen.len(), 3);
+        assert_eq!(seen[2].total_seconds, Some(10.0));
+        assert_eq!(seen[2].decoded_seconds, 2.0);
+    }
+
+    #[test]
+    fn transcribed_seconds_never_move_backwards() {
+        let (reporter, seen) = recording_reporter();
+        reporter.set_transcribed_seconds(4.0);
+        reporter.set_transcribed_seconds(3.0);
+
+        let last = *seen.lock().expect("lock").last().expect("snapshot");
+        assert_eq!(last.transcribed_seconds, 4.0);
+    }
+
+    #[test]
+    fn passes_are_reported_as_one_run() {
+        let (reporter, seen) = recording_reporter();
+        reporter.set_passes(2);
+        reporter.start_pass();
+        reporter.set_total_seconds(4.0);
+        reporter.add_decoded_seconds(4.0);
+        reporter.set_transcribed_seconds(4.0);
+        reporter.start_pass();
+        reporter.set_total_seconds(4.0);
+        reporter.add_decoded_seconds(4.0);
+        reporter.set_transcribed_seconds(1.0);
+
+        let seen = seen.lock().expect("lock");
+        let last = *seen.last().expect("snapshot");
+        assert_eq!(last.total_seconds, Some(8.0));
+        assert_eq!(last.decoded_seconds, 8.0);
+        assert_eq!(last.transcribed_seconds, 5.0);
+        assert!(
+            seen.windows(2)
+                .all(|w| w[0].transcribed_seconds <= w[1].transcribed_seconds)
+        );
+    }
+
+    #[test]
+    fn finish_marks_all_decoded_audio_as_transcribed() {
+        let (reporter, seen) = recording_reporter();
+        reporter.add_decoded_seconds(7.0);
+        reporter.finish();
+
+        let last = *seen.lock().expect("lock").last().expect("snapshot");
+        assert_eq!(last.transcribed_seconds, 7.0);
+    }
+}
@@ -0,0 +1,223 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Highest sample rate accepted for raw input (Hz); well above any real capture rate.
+const MAX_SAMPLE_RATE: u32 = 384_000;
+
+/// Most channels accepted for raw input.
+const MAX_CHANNELS: usize = 32;
+
+/// Sample encoding of headerless PCM input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RawSampleEncoding {
+    /// Signed 16-bit little-endian linear PCM.
+    S16le,
+
+    /// 8-bit G.711 µ-law, common in North American and Japanese telephony.
+    Ulaw,
+
+    /// 8-bit G.711 A-law, common in European telephony.
+    Alaw,
+}
+
+impl RawSampleEncoding {
+    /// Bytes per sample (of one channel).
+    pub fn bytes_per_sample(self) -> usize {
+        match self {
+            Self::S16le => 2,
+            Self::Ulaw | Self::Alaw => 1,
+        }
+    }
+}
+
+/// Layout of headerless PCM input (e.g. a telephony gateway's raw stream).
+///
+/// Raw input skips container probing entirely, so the format has to be known up front. Build one
+/// with [`RawAudioFormat::new`] or parse it from `encoding:sample_rate:channels`, e.g.
+/// `"s16le:8000:1"` or `"ulaw:8000:1"`; sample rates from 1 Hz to 384 kHz and 1 to 32 channels
+/// are accepted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RawAudioFormat {
+    encoding: RawSampleEncoding,
+    sample_rate: u32,
+    channels: usize,
+}
+
+impl RawAudioFormat {
+    /// Validate a raw layout, rejecting zero or out-of-range sample rates and channel counts.
+    pub fn new(
+        encoding: RawSampleEncoding,
+        sample_rate: u32,
+        channels: usize,
+    ) -> crate::Result<Self> {
+        if !(1..=MAX_SAMPLE_RATE).contains(&sample_rate) || !(1..=MAX_CHANNELS).contains(&channels)
+        {
+            return Err(crate::Error::invalid_input(format!(
+                "raw audio needs 1 to {MAX_SAMPLE_RATE} Hz and 1 to {MAX_CHANNELS} channels (got {sample_rate} Hz, {channels} channels)"
+            )));
+        }
+        Ok(Self {
+            encoding,
+            sample_rate,
+            channels,
+        })
+    }
+
+    /// How each sample is encoded.
+    pub fn encoding(&self) -> RawSampleEncoding {
+        self.encoding
+    }
+
+    /// Sample rate in Hz.
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Number of interleaved channels.
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// Bytes per frame (one sample for every channel).
+    pub fn bytes_per_frame(&self) -> usize {
+        self.encoding.bytes_per_sample() * self.channels
+    }
+
+    /// Decode whole samples from `bytes` into interleaved `f32` in `[-1.0, 1.0)`, appending to
+    /// `out`. Trailing bytes that do not form a whole sample are ignored.
+    pub(crate) fn decode_into(&self, bytes: &[u8], out: &mut Vec<f32>) {
+        let scale = |sample: i16| sample as f32 / 32_768.0;
+        match self.encoding {
+            RawSampleEncoding::S16le => out.extend(
+                bytes
+                    .chunks_exact(2)
+                    .map(|pair| scale(i16::from_le_bytes([pair[0], pair[1]]))),
+            ),
+            RawSampleEncoding::Ulaw => out.extend(bytes.iter().map(|&b| scale(ulaw_to_i16(b)))),
+            RawSampleEncoding::Alaw => out.extend(bytes.iter().map(|&b| scale(alaw_to_i16(b)))),
+        }
+    }
+}
+
+impl FromStr for RawAudioFormat {
+    type Err = crate::Error;
+
+    /// Parse `encoding:sample_rate:channels`, e.g. `"s16le:8000:1"`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            crate::Error::invalid_input(format!(
+                "raw format '{s}' must look like ENCODING:SAMPLE_RATE:CHANNELS (e.g. s16le:8000:1), with ENCODING one of s16le, ulaw, alaw"
+            ))
+        };
+
+        let mut parts = s.trim().split(':');
+        let (Some(encoding), Some(sample_rate), Some(channels), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid());
+        };
+
+        let encoding = match encoding.to_ascii_lowercase().as_str() {
+            "s16le" => RawSampleEncoding::S16le,
+            "ulaw" | "mulaw" => RawSampleEncoding::Ulaw,
+            "alaw" => RawSampleEncoding::Alaw,
+            _ => return Err(invalid()),
+        };
+        let sample_rate: u32 = sample_rate.parse().map_err(|_| invalid())?;
+        let channels: usize = channels.parse().map_err(|_| invalid())?;
+        Self::new(encoding, sample_rate, channels)
+    }
+}
+
+impl fmt::Display for RawAudioFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let encoding = match self.encoding {
+            RawSampleEncoding::S16le => "s16le",
+            RawSampleEncoding::Ulaw => "ulaw",
+            RawSampleEncoding::Alaw => "alaw",
+        };
+        write!(f, "{encoding}:{}:{}", self.sample_rate, self.channels)
+    }
+}
+
+/// Expand a G.711 µ-law byte to 16-bit linear PCM.
+fn ulaw_to_i16(byte: u8) -> i16 {
+    let byte = !byte;
+    let exponent = (byte >> 4) & 0x07;
+    let mantissa = i32::from(byte & 0x0F);
+    let magnitude = (((mantissa << 3) + 0x84) << exponent) - 0x84;
+    if byte & 0x80 != 0 {
+        -magnitude as i16
+    } else {
+        magnitude as i16
+    }
+}
+
+/// Expand a G.711 A-law byte to 16-bit linear PCM.
+fn alaw_to_i16(byte: u8) -> i16 {
+    let byte = byte ^ 0x55;
+    let exponent = (byte >> 4) & 0x07;
+    let mantissa = i32::from(byte & 0x0F);
+    let magnitude = match exponent {
+        0 => (mantissa << 4) + 8,
+        _ => ((mantissa << 4) + 0x108) << (exponent - 1),
+    };
+    // Unlike µ-law, a set sign bit means positive.
+    if byte & 0x80 != 0 {
+        magnitude as i16
+    } else {
+        -magnitude as i16
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_displays_raw_formats() -> anyhow::Result<()> {
+        let format: RawAudioFormat = "ULAW:8000:2".parse()?;
+        assert_eq!(
+            format,
+            RawAudioFormat::new(RawSampleEncoding::Ulaw, 8000, 2)?
+        );
+        assert_eq!(format.to_string(), "ulaw:8000:2");
+        assert_eq!(format.bytes_per_frame(), 2);
+
+        for bad in ["s16le:8000", "s24le:8000:1", "alaw:0:1", "s16le:8000:1:1"] {
+            assert!(bad.parse::<RawAudioFormat>().is_err(), "{bad}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_oversized_raw_formats() -> anyhow::Result<()> {
+        for bad in ["s16le:4000000000:4096", "s16le:384001:1", "ulaw:8000:33"] {
+            let err = bad.parse::<RawAudioFormat>().unwrap_err();
+            assert!(matches!(err, crate::Error::InvalidInput { .. }), "{bad}");
+        }
+        "s16le:384000:32".parse::<RawAudioFormat>()?;
+        Ok(())
+    }
+
+    #[test]
+    fn new_rejects_zero_rates_and_channels() {
+        for (sample_rate, channels) in [(0, 1), (8000, 0), (MAX_SAMPLE_RATE + 1, 1)] {
+            let err =
+                RawAudioFormat::new(RawSampleEncoding::S16le, sample_rate, channels).unwrap_err();
+            assert!(matches!(err, crate::Error::InvalidInput { .. }));
+        }
+    }
+
+    #[test]
+    fn g711_matches_reference_values() {
+        // Silence and full scale, per the G.711 tables.
+        assert_eq!(ulaw_to_i16(0xFF), 0);
+        assert_eq!(ulaw_to_i16(0x80), 32_124);
+        assert_eq!(ulaw_to_i16(0x00), -32_124);
+        assert_eq!(alaw_to_i16(0xD5), 8);
+        assert_eq!(alaw_to_i16(0x55), -8);
+        assert_eq!(alaw_to_i16(0xAA), 32_256);
+        assert_eq!(alaw_to_i16(0x2A), -32_256);
+    }
+}
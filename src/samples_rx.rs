@@ -9,7 +9,8 @@
 //! `SamplesRx` provides a receiver-like shape without introducing trait objects or implicit
 //! behavior.
 
-use std::sync::mpsc;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
 
 use anyhow::{Result, anyhow};
 
@@ -40,6 +41,23 @@ impl SamplesRx {
             SamplesRx::Vad(rx) => rx.recv(),
         }
     }
+
+    /// Like [`recv`](Self::recv), but gives up after `timeout`.
+    ///
+    /// Returns `Ok(None)` when the timeout passes first, so callers can enforce a deadline while
+    /// the decoder is stalled on its input.
+    pub fn recv_timeout(&mut self, timeout: Duration) -> Result<Option<Vec<f32>>> {
+        match self {
+            SamplesRx::Plain(rx) => match rx.recv_timeout(timeout) {
+                Ok(chunk) => Ok(Some(chunk)),
+                Err(RecvTimeoutError::Timeout) => Ok(None),
+                Err(RecvTimeoutError::Disconnected) => {
+                    Err(anyhow!("decoder output channel disconnected"))
+                }
+            },
+            SamplesRx::Vad(rx) => rx.recv_timeout(timeout),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -57,4 +75,21 @@ mod tests {
                 .contains("decoder output channel disconnected")
         );
     }
+
+    #[test]
+    fn samples_rx_plain_times_out_while_sender_is_alive() {
+        let (tx, rx) = mpsc::channel::<Vec<f32>>();
+        let mut rx = SamplesRx::Plain(rx);
+        assert!(
+            rx.recv_timeout(Duration::from_millis(10))
+                .unwrap()
+                .is_none()
+        );
+
+        tx.send(vec![0.5]).unwrap();
+        assert_eq!(
+            rx.recv_timeout(Duration::from_millis(10)).unwrap(),
+            Some(vec![0.5])
+        );
+    }
 }
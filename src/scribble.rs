@@ -11,19 +11,30 @@
 //! This module is deliberately “high level”: it wires up decoding → backend → encoder,
 //! while keeping the lower-level pieces testable in their own modules.
 
-use std::io::{BufWriter, Read, Write};
-use std::sync::mpsc;
+use std::io::{BufWriter, Read, Seek, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, mpsc};
+use std::time::{Duration, Instant};
 
 use crate::Result;
+use crate::audio_pipeline::TARGET_SAMPLE_RATE;
 use crate::backend::{Backend, BackendStream};
 use crate::backends::whisper::WhisperBackend;
-use crate::decoder::{SamplesSink, StreamDecodeOpts, decode_to_stream_from_read};
+use crate::decoder::{
+    DecodeInput, MAX_SPOOL_BYTES, ReplayableInput, SamplesSink, StreamDecodeOpts, decode_to_stream,
+};
+use crate::hallucination_filter::FilteringEncoder;
 use crate::json_array_encoder::JsonArrayEncoder;
+use crate::json_lines_encoder::JsonLinesEncoder;
 use crate::opts::Opts;
 use crate::output_type::OutputType;
+use crate::progress::{ProgressEncoder, ProgressReporter};
 use crate::samples_rx::SamplesRx;
-use crate::segment_encoder::SegmentEncoder;
-use crate::vad::{VadProcessor, VadStreamReceiver};
+use crate::segment_encoder::{OffsetEncoder, SegmentEncoder};
+use crate::segments::Segment;
+use crate::track_selection::TrackSelection;
+use crate::transcription_mode::TranscriptionMode;
+use crate::vad::{VadProcessor, VadStreamReceiver, split_at_silences};
 use crate::vtt_encoder::VttEncoder;
 
 /// The main high-level transcription entry point.
@@ -78,12 +89,38 @@ impl<B: Backend> Scribble<B> {
     /// Decodes audio into a mono 16kHz stream (whisper.cpp’s expected format) using the `decoder`
     /// module, optionally applies VAD, and then runs the backend and encodes segments.
     ///
+    /// Some containers (notably MP4/MOV files with a trailing `moov` box) can only be decoded
+    /// from a seekable input; use [`Scribble::transcribe_seekable`] for those.
+    ///
     /// Note: The `Send + 'static` bounds mirror the decoder API.
     pub fn transcribe<R, W>(&self, r: R, w: W, opts: &Opts) -> Result<()>
     where
         R: Read + Send + 'static,
         W: Write,
     {
+        self.transcribe_input(DecodeInput::Stream(Box::new(r)), w, opts)
+    }
+
+    /// Transcribe a seekable input (typically a `File`) and write the result to an output writer.
+    ///
+    /// Same as [`Scribble::transcribe`], but the decoder may seek: containers that keep their
+    /// metadata at the end (e.g. MP4/MOV with a trailing `moov` box) decode correctly, and
+    /// `opts.start_seconds` seeks instead of decoding the audio before it.
+    pub fn transcribe_seekable<R, W>(&self, r: R, w: W, opts: &Opts) -> Result<()>
+    where
+        R: Read + Seek + Send + 'static,
+        W: Write,
+    {
+        self.transcribe_input(DecodeInput::Seekable(Box::new(r)), w, opts)
+    }
+
+    pub(crate) fn transcribe_input<W>(&self, input: DecodeInput, w: W, opts: &Opts) -> Result<()>
+    where
+        W: Write,
+    {
+        // Reject unsupported options before anything is written.
+        self.validate_opts(opts)?;
+
         // Buffer output for efficiency (especially important for stdout).
         let writer = BufWriter::new(w);
 
@@ -92,45 +129,237 @@ impl<B: Backend> Scribble<B> {
         match opts.output_type {
             OutputType::Json => {
                 let mut encoder = JsonArrayEncoder::new(writer);
-                let run_res = self.transcribe_with_encoder(r, opts, &mut encoder);
+                let run_res = self.transcribe_input_with_encoder(input, opts, &mut encoder);
                 merge_run_and_close(run_res, encoder.close())
             }
             OutputType::Vtt => {
                 let mut encoder = VttEncoder::new(writer);
-                let run_res = self.transcribe_with_encoder(r, opts, &mut encoder);
+                let run_res = self.transcribe_input_with_encoder(input, opts, &mut encoder);
+                merge_run_and_close(run_res, encoder.close())
+            }
+            OutputType::JsonLines => {
+                let mut encoder = JsonLinesEncoder::new(writer);
+                let run_res = self.transcribe_input_with_encoder(input, opts, &mut encoder);
                 merge_run_and_close(run_res, encoder.close())
             }
         }
     }
 
-    fn transcribe_with_encoder<R, E>(&self, r: R, opts: &Opts, encoder: &mut E) -> Result<()>
+    /// [`Scribble::transcribe_input_with_encoder`] for a plain byte stream.
+    #[cfg(test)]
+    pub(crate) fn transcribe_with_encoder<R, E>(
+        &self,
+        r: R,
+        opts: &Opts,
+        encoder: &mut E,
+    ) -> Result<()>
     where
         R: Read + Send + 'static,
         E: SegmentEncoder,
     {
-        let backend = &self.backend;
+        self.transcribe_input_with_encoder(DecodeInput::Stream(Box::new(r)), opts, encoder)
+    }
+
+    pub(crate) fn transcribe_input_with_encoder<E>(
+        &self,
+        input: DecodeInput,
+        opts: &Opts,
+        encoder: &mut E,
+    ) -> Result<()>
+    where
+        E: SegmentEncoder,
+    {
+        let progress = opts
+            .progress_listener
+            .clone()
+            .map(|listener| Arc::new(ProgressReporter::new(listener)));
+
+        let control = DecodeControl::new(opts);
+
+        if opts.enable_per_channel_transcription {
+            return self.transcribe_per_channel(input, opts, encoder, &control, progress);
+        }
+
+        self.transcribe_pass(input, opts, encoder, &control, progress.clone())?;
+        if let Some(progress) = &progress {
+            progress.finish();
+        }
+        Ok(())
+    }
+
+    /// Transcribe the audio `control` selects from `input` with the configured mode.
+    fn transcribe_pass(
+        &self,
+        input: DecodeInput,
+        opts: &Opts,
+        encoder: &mut dyn SegmentEncoder,
+        control: &DecodeControl,
+        progress: Option<Arc<ProgressReporter>>,
+    ) -> Result<()> {
+        // Wrapped outermost to innermost: progress, offset, filter. Progress sees every segment
+        // before the filter runs, so dropped segments still advance transcribed time, and it
+        // tracks time within the requested range before the offset makes timestamps relative
+        // to the start of the original media.
+        let mut filtered = FilteringEncoder::new(encoder, opts.hallucination_filter.as_ref());
+        let mut shifted = OffsetEncoder::new(&mut filtered, opts.start_seconds.unwrap_or(0.0));
+        let mut encoder = ProgressEncoder::new(&mut shifted, progress.clone());
+        match opts.transcription_mode {
+            TranscriptionMode::Streaming => {
+                self.transcribe_streaming(input, opts, &mut encoder, control, progress)
+            }
+            TranscriptionMode::Full { max_seconds } => {
+                if max_seconds == 0 {
+                    return Err(crate::Error::invalid_input(
+                        "full-buffer transcription needs a non-zero maximum duration",
+                    ));
+                }
+                self.transcribe_full_buffer(
+                    input,
+                    opts,
+                    &mut encoder,
+                    control,
+                    progress,
+                    max_seconds,
+                )
+            }
+            TranscriptionMode::ParallelChunks {
+                workers,
+                chunk_seconds,
+            } => {
+                if workers == 0 || chunk_seconds == 0 {
+                    return Err(crate::Error::invalid_input(
+                        "parallel transcription needs at least one worker and a non-zero chunk length",
+                    ));
+                }
+                self.transcribe_parallel_chunks(
+                    input,
+                    opts,
+                    &mut encoder,
+                    control,
+                    progress,
+                    chunk_seconds,
+                )
+            }
+        }
+    }
+
+    /// Transcribe each channel of the selected track on its own and merge the segments by start
+    /// time, tagging each with its channel.
+    ///
+    /// The input is decoded once per channel, one channel after another, so segments are only
+    /// written once every channel is done. Progress covers all passes as one run, and the
+    /// wall-clock budget is shared out between the channels still to go.
+    fn transcribe_per_channel(
+        &self,
+        input: DecodeInput,
+        opts: &Opts,
+        encoder: &mut dyn SegmentEncoder,
+        control: &DecodeControl,
+        progress: Option<Arc<ProgressReporter>>,
+    ) -> Result<()> {
+        if opts.audio_track == TrackSelection::All {
+            return Err(crate::Error::invalid_input(
+                "per-channel transcription needs a single audio track, not \"all\"",
+            ));
+        }
+
+        // Stops copying a stalled or over-long stream once the wall-clock budget runs out; the
+        // passes then transcribe what was read, as with any other early stop.
+        let input = ReplayableInput::new(input, MAX_SPOOL_BYTES, || {
+            if opts.is_cancelled() {
+                return Err(crate::Error::Cancelled.into());
+            }
+            Ok(!control.should_stop())
+        })?;
+        let channels = input.channel_count(&StreamDecodeOpts {
+            hint_extension: opts.input_format.clone(),
+            raw_format: opts.raw_input_format,
+            track: opts.audio_track.clone(),
+            ..StreamDecodeOpts::default()
+        })?;
+
+        if let Some(progress) = &progress {
+            progress.set_passes(channels);
+        }
+        let mut collected = ChannelCollector::default();
+        for channel in 0..channels {
+            collected.channel = channel;
+            if let Some(progress) = &progress {
+                progress.start_pass();
+            }
+            self.transcribe_pass(
+                input.rewound()?,
+                opts,
+                &mut collected,
+                &control.for_channel(channel, channels - channel),
+                progress.clone(),
+            )?;
+        }
+
+        // Stable, so each channel's segments keep their order when start times tie.
+        collected
+            .segments
+            .sort_by(|a, b| a.start_seconds.total_cmp(&b.start_seconds));
+        for seg in &collected.segments {
+            encoder.write_segment(seg)?;
+        }
+
+        if let Some(progress) = &progress {
+            progress.finish();
+        }
+        Ok(())
+    }
+
+    /// Feed decoded audio to a backend stream as it arrives.
+    fn transcribe_streaming(
+        &self,
+        input: DecodeInput,
+        opts: &Opts,
+        encoder: &mut dyn SegmentEncoder,
+        control: &DecodeControl,
+        progress: Option<Arc<ProgressReporter>>,
+    ) -> Result<()> {
         let vad = Self::get_vad(self.vad_model_path.as_deref(), opts)?;
 
         // Decode on a dedicated thread to overlap I/O + decode with backend inference.
         // Keep orchestration and error plumbing on the calling thread.
-        let (mut rx, decode_handle) = Self::get_samples_rx(r, opts, vad)?;
+        let (mut rx, decode_handle) = Self::get_samples_rx(input, opts, vad, control, progress)?;
 
-        let mut stream = backend.create_stream(opts, encoder)?;
+        let mut stream = self.backend.create_stream(opts, encoder)?;
 
         // Consume decoded chunks as they arrive. This can run Whisper and emit segments while the
         // decode thread continues reading.
-        while let Ok(chunk) = rx.recv() {
-            let _ = stream.on_samples(&chunk)?;
+        let mut stopped_early = false;
+        while let Ok(received) = control.recv(&mut rx) {
+            if opts.is_cancelled() {
+                // Returning drops the receiver, so the decode thread's next send fails and it
+                // exits on its own. It is not joined: a stalled input (e.g. stdin) would block.
+                return Err(crate::Error::Cancelled);
+            }
+            let Some(chunk) = received else {
+                stopped_early = true;
+                break;
+            };
+            if !stream.on_samples(&chunk)? || control.should_stop() {
+                stopped_early = true;
+                break;
+            }
         }
+        // Stop the decode thread (a no-op if the input already ended). Dropping the receiver
+        // unblocks it if it is waiting on a full channel.
+        control.stop();
+        drop(rx);
 
         // Always call `finish()` so the backend can flush any buffered segments.
         let finish_res = stream.finish();
 
         // If decode failed, surface that error (but still prefer a backend/transcription error
-        // if both happened). This keeps failure reporting stable and unsurprising.
-        let decode_res: Result<()> = match decode_handle.join() {
-            Ok(res) => res,
-            Err(_) => Err(anyhow::anyhow!("audio decoder thread panicked").into()),
+        // if both happened). This keeps failure reporting stable and unsurprising. After an
+        // early stop the decoder is detached instead, like on cancellation.
+        let decode_res = if stopped_early {
+            Ok(())
+        } else {
+            join_decoder(decode_handle)
         };
 
         match (finish_res, decode_res) {
@@ -143,6 +372,110 @@ impl<B: Backend> Scribble<B> {
         }
     }
 
+    /// Decode the whole input and hand it to the backend in one `transcribe_full` call.
+    fn transcribe_full_buffer(
+        &self,
+        input: DecodeInput,
+        opts: &Opts,
+        encoder: &mut dyn SegmentEncoder,
+        control: &DecodeControl,
+        progress: Option<Arc<ProgressReporter>>,
+        max_seconds: usize,
+    ) -> Result<()> {
+        let vad = Self::get_vad(self.vad_model_path.as_deref(), opts)?;
+
+        let mut samples = Self::decode_all(input, opts, control, progress, Some(max_seconds))?;
+
+        if let Some(mut vad) = vad {
+            // Same semantics as streaming VAD: no speech means no output.
+            if !vad.apply(&mut samples)? {
+                return Ok(());
+            }
+        }
+
+        self.backend.transcribe_full(opts, encoder, &samples)
+    }
+
+    /// Decode the whole input into memory.
+    ///
+    /// Fails with `InvalidInput` as soon as the audio exceeds `max_seconds`; dropping the
+    /// receiver then stops the decode thread without reading the rest of the input.
+    fn decode_all(
+        input: DecodeInput,
+        opts: &Opts,
+        control: &DecodeControl,
+        progress: Option<Arc<ProgressReporter>>,
+        max_seconds: Option<usize>,
+    ) -> Result<Vec<f32>> {
+        let (rx, decode_handle) = Self::spawn_decoder(input, opts, control, progress);
+        let mut rx = SamplesRx::Plain(rx);
+        let mut samples = Vec::new();
+        while let Ok(received) = control.recv(&mut rx) {
+            if opts.is_cancelled() {
+                return Err(crate::Error::Cancelled);
+            }
+            let Some(chunk) = received else {
+                // Out of time while the decoder is stalled: keep what arrived and leave the
+                // decode thread detached rather than waiting on its input.
+                control.stop();
+                return Ok(samples);
+            };
+            samples.extend_from_slice(&chunk);
+            if let Some(max_seconds) = max_seconds
+                && samples.len() > max_seconds.saturating_mul(TARGET_SAMPLE_RATE as usize)
+            {
+                return Err(crate::Error::invalid_input(format!(
+                    "input is longer than {max_seconds}s, the limit for full-buffer transcription; use streaming or parallel mode instead"
+                )));
+            }
+            if control.should_stop() {
+                control.stop();
+                return Ok(samples);
+            }
+        }
+        join_decoder(decode_handle)?;
+        Ok(samples)
+    }
+
+    /// Decode the whole input, split it at silences, and let the backend transcribe the chunks
+    /// (concurrently, when it supports that).
+    fn transcribe_parallel_chunks(
+        &self,
+        input: DecodeInput,
+        opts: &Opts,
+        encoder: &mut dyn SegmentEncoder,
+        control: &DecodeControl,
+        progress: Option<Arc<ProgressReporter>>,
+        chunk_seconds: usize,
+    ) -> Result<()> {
+        // Split points come from VAD even when VAD filtering is off; cutting mid-word would
+        // damage the words on both sides of the cut.
+        let Some(vad_model_path) = self.vad_model_path.as_deref() else {
+            return Err(crate::Error::invalid_input(
+                "parallel transcription needs a VAD model to find split points, but none is configured for this Scribble instance",
+            ));
+        };
+        let mut vad = VadProcessor::new(vad_model_path)?;
+
+        let mut samples = Self::decode_all(input, opts, control, progress, None)?;
+
+        let speech = vad.speech_ranges(&samples)?;
+        if opts.enable_voice_activity_detection {
+            if speech.is_empty() {
+                return Ok(());
+            }
+            vad.attenuate_non_speech(&mut samples, &speech);
+        }
+
+        let chunks = split_at_silences(
+            &speech,
+            samples.len(),
+            chunk_seconds.saturating_mul(TARGET_SAMPLE_RATE as usize),
+        );
+        self.backend
+            .transcribe_chunks(opts, encoder, &samples, &chunks)
+    }
+
     fn get_vad(vad_model_path: Option<&str>, opts: &Opts) -> Result<Option<VadProcessor>> {
         if !opts.enable_voice_activity_detection {
             return Ok(None);
@@ -169,24 +502,15 @@ impl<B: Backend> Scribble<B> {
         Ok(Some(VadProcessor::new(vad_model_path)?))
     }
 
-    fn get_samples_rx<R>(
-        r: R,
+    fn get_samples_rx(
+        input: DecodeInput,
         opts: &Opts,
         vad: Option<VadProcessor>,
-    ) -> Result<(SamplesRx, std::thread::JoinHandle<Result<()>>)>
-    where
-        R: Read + Send + 'static,
-    {
-        // Use a bounded channel to keep memory usage predictable if the backend is slower than
-        // decoding. This also makes backpressure explicit rather than relying on unbounded queues.
-        let (tx, rx) = mpsc::sync_channel::<Vec<f32>>(512);
-        let decode_opts = StreamDecodeOpts::default();
-        let emit_frames = decode_opts.target_chunk_frames;
-
-        let decode_handle = std::thread::spawn(move || -> Result<()> {
-            let mut sink = ChannelSamplesSink { tx };
-            decode_to_stream_from_read(r, decode_opts, &mut sink).map_err(Into::into)
-        });
+        control: &DecodeControl,
+        progress: Option<Arc<ProgressReporter>>,
+    ) -> Result<(SamplesRx, std::thread::JoinHandle<Result<()>>)> {
+        let emit_frames = StreamDecodeOpts::default().target_chunk_frames;
+        let (rx, decode_handle) = Self::spawn_decoder(input, opts, control, progress);
 
         let rx = if opts.enable_voice_activity_detection {
             // Wrap the decoder receiver so the main loop stays unchanged when VAD is enabled.
@@ -203,6 +527,56 @@ impl<B: Backend> Scribble<B> {
         Ok((rx, decode_handle))
     }
 
+    /// Start decoding `input` on a dedicated thread, returning the channel of decoded chunks.
+    ///
+    /// The thread stops early once `control` says so.
+    fn spawn_decoder(
+        input: DecodeInput,
+        opts: &Opts,
+        control: &DecodeControl,
+        progress: Option<Arc<ProgressReporter>>,
+    ) -> (
+        mpsc::Receiver<Vec<f32>>,
+        std::thread::JoinHandle<Result<()>>,
+    ) {
+        // Use a bounded channel to keep memory usage predictable if the backend is slower than
+        // decoding. This also makes backpressure explicit rather than relying on unbounded queues.
+        let (tx, rx) = mpsc::sync_channel::<Vec<f32>>(512);
+        let decode_opts = StreamDecodeOpts {
+            hint_extension: opts.input_format.clone(),
+            raw_format: opts.raw_input_format,
+            start_seconds: Some(control.start_seconds()).filter(|&seconds| seconds > 0.0),
+            track: opts.audio_track.clone(),
+            channel: control.channel,
+            ..StreamDecodeOpts::default()
+        };
+
+        let control = control.clone();
+        let decode_handle = std::thread::spawn(move || -> Result<()> {
+            let mut sink = ChannelSamplesSink {
+                tx,
+                progress,
+                control,
+                position: 0,
+            };
+            decode_to_stream(input, decode_opts, &mut sink).map_err(Into::into)
+        });
+
+        (rx, decode_handle)
+    }
+
+    /// Check `opts` against the [`Backend::capabilities`] of the model it selects, and check that
+    /// its time range can select any audio.
+    ///
+    /// [`Scribble::transcribe`] does this itself; call it directly to reject a request before
+    /// committing to a response (e.g. in a server).
+    pub fn validate_opts(&self, opts: &Opts) -> Result<()> {
+        validate_range(opts)?;
+        self.backend
+            .capabilities(opts.model_key.as_deref())
+            .validate(opts)
+    }
+
     /// Access the configured backend.
     pub fn backend(&self) -> &B {
         &self.backend
@@ -214,6 +588,31 @@ impl<B: Backend> Scribble<B> {
     }
 }
 
+/// Reject time ranges that cannot select any audio.
+fn validate_range(opts: &Opts) -> Result<()> {
+    let valid = |seconds: Option<f32>| seconds.is_none_or(|s| s.is_finite() && s >= 0.0);
+    if !valid(opts.start_seconds) || !valid(opts.end_seconds) {
+        return Err(crate::Error::invalid_input(
+            "start and end offsets must be non-negative numbers of seconds",
+        ));
+    }
+    if let (Some(start), Some(end)) = (opts.start_seconds, opts.end_seconds)
+        && start >= end
+    {
+        return Err(crate::Error::invalid_input(format!(
+            "start offset ({start}s) must be before end offset ({end}s)"
+        )));
+    }
+    Ok(())
+}
+
+fn join_decoder(handle: std::thread::JoinHandle<Result<()>>) -> Result<()> {
+    match handle.join() {
+        Ok(res) => res,
+        Err(_) => Err(anyhow::anyhow!("audio decoder thread panicked").into()),
+    }
+}
+
 fn merge_run_and_close(run_res: Result<()>, close_res: Result<()>) -> Result<()> {
     match (run_res, close_res) {
         (Ok(()), Ok(())) => Ok(()),
@@ -225,25 +624,180 @@ fn merge_run_and_close(run_res: Result<()>, close_res: Result<()>) -> Result<()>
     }
 }
 
+/// Tells the decode thread which part of the input to emit and when to stop early.
+///
+/// Shared between the decode thread and the consumer: the consumer calls `stop` when the backend
+/// asks to stop, and both sides enforce `Opts::max_wall_clock_seconds`. The sink trims the input
+/// to `start_samples..end_samples` (source positions at the target sample rate).
+#[derive(Clone)]
+struct DecodeControl {
+    stopped: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+    start_samples: usize,
+    end_samples: Option<usize>,
+    /// Source channel to decode, or `None` to downmix.
+    channel: Option<usize>,
+}
+
+impl DecodeControl {
+    fn new(opts: &Opts) -> Self {
+        let to_samples = |seconds: f64| (seconds * TARGET_SAMPLE_RATE as f64).round() as usize;
+        let start_samples = opts.start_seconds.map_or(0, |s| to_samples(s as f64));
+        let range_end = opts.end_seconds.map(|s| to_samples(s as f64));
+        let audio_end = opts
+            .max_audio_seconds
+            .map(|s| start_samples.saturating_add(to_samples(s as f64)));
+
+        Self {
+            stopped: Arc::new(AtomicBool::new(false)),
+            // A budget too large to represent as an instant can never run out.
+            deadline: opts.max_wall_clock_seconds.and_then(|seconds| {
+                Instant::now().checked_add(Duration::from_secs(seconds as u64))
+            }),
+            start_samples,
+            end_samples: match (range_end, audio_end) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (end, None) | (None, end) => end,
+            },
+            channel: None,
+        }
+    }
+
+    /// The same limits for a fresh pass over `channel`, with an even share of the remaining
+    /// wall-clock time among the `passes_left` passes (this one included).
+    ///
+    /// Time a pass does not use carries over to the passes after it.
+    fn for_channel(&self, channel: usize, passes_left: usize) -> Self {
+        let now = Instant::now();
+        let share = u32::try_from(passes_left.max(1)).unwrap_or(u32::MAX);
+        Self {
+            stopped: Arc::new(AtomicBool::new(false)),
+            deadline: self
+                .deadline
+                .map(|deadline| now + deadline.saturating_duration_since(now) / share),
+            channel: Some(channel),
+            ..self.clone()
+        }
+    }
+
+    fn start_seconds(&self) -> f64 {
+        self.start_samples as f64 / TARGET_SAMPLE_RATE as f64
+    }
+
+    fn stop(&self) {
+        self.stopped.store(true, Ordering::SeqCst);
+    }
+
+    fn is_stopped(&self) -> bool {
+        self.stopped.load(Ordering::SeqCst)
+    }
+
+    /// Receive the next decoded chunk, waking up at the wall-clock deadline.
+    ///
+    /// `Ok(None)` means the deadline passed while waiting; an error means the input ended.
+    fn recv(&self, rx: &mut SamplesRx) -> anyhow::Result<Option<Vec<f32>>> {
+        match self.deadline {
+            Some(deadline) => rx.recv_timeout(deadline.saturating_duration_since(Instant::now())),
+            None => rx.recv().map(Some),
+        }
+    }
+
+    fn should_stop(&self) -> bool {
+        self.is_stopped()
+            || self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+}
+
+/// Collects one channel's segments at a time for per-channel transcription.
+#[derive(Default)]
+struct ChannelCollector {
+    segments: Vec<Segment>,
+    channel: usize,
+}
+
+impl SegmentEncoder for ChannelCollector {
+    fn write_segment(&mut self, seg: &Segment) -> Result<()> {
+        self.segments.push(Segment {
+            channel: Some(self.channel),
+            ..seg.clone()
+        });
+        Ok(())
+    }
+
+    fn close(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
 struct ChannelSamplesSink {
     tx: mpsc::SyncSender<Vec<f32>>,
+    progress: Option<Arc<ProgressReporter>>,
+    control: DecodeControl,
+    /// Source position (samples at the target rate) of the next decoded sample.
+    position: usize,
 }
 
 impl SamplesSink for ChannelSamplesSink {
     fn on_samples(&mut self, samples_16k_mono: &[f32]) -> anyhow::Result<bool> {
+        if self.control.should_stop() {
+            return Ok(false);
+        }
+
+        // Keep only the part of the chunk inside the requested range, and stop once past it.
+        let chunk_start = self.position;
+        let chunk_end = chunk_start + samples_16k_mono.len();
+        self.position = chunk_end;
+        let from = self.control.start_samples.clamp(chunk_start, chunk_end);
+        let to = self
+            .control
+            .end_samples
+            .map_or(chunk_end, |end| end.clamp(from, chunk_end));
+        let keep_going = self.control.end_samples.is_none_or(|end| chunk_end < end);
+        let samples = &samples_16k_mono[from - chunk_start..to - chunk_start];
+        if samples.is_empty() {
+            return Ok(keep_going);
+        }
+
         // Copy into an owned buffer so the decoder thread can send it across threads safely.
         // Whisper inference runs on the receiver side.
-        let buf = samples_16k_mono.to_vec();
-        self.tx
-            .send(buf)
-            .map_err(|_| anyhow::anyhow!("decoder output channel disconnected"))?;
-        Ok(true)
+        let buf = samples.to_vec();
+        if self.tx.send(buf).is_err() {
+            // The consumer hung up on purpose after asking to stop.
+            if self.control.is_stopped() {
+                return Ok(false);
+            }
+            return Err(anyhow::anyhow!("decoder output channel disconnected"));
+        }
+
+        if let Some(progress) = &self.progress {
+            progress.add_decoded_seconds(samples.len() as f64 / TARGET_SAMPLE_RATE as f64);
+        }
+        Ok(keep_going)
+    }
+
+    /// Reports the length of the requested range rather than of the whole input.
+    fn on_total_duration(&mut self, total_seconds: f64) {
+        let end_seconds = match self.control.end_samples {
+            Some(end) => total_seconds.min(end as f64 / TARGET_SAMPLE_RATE as f64),
+            None => total_seconds,
+        };
+        if let Some(progress) = &self.progress {
+            progress.set_total_seconds((end_seconds - self.control.start_seconds()).max(0.0));
+        }
+    }
+
+    fn on_seek(&mut self, position_seconds: f64) {
+        self.position = (position_seconds * TARGET_SAMPLE_RATE as f64).round() as usize;
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    use crate::track_selection::TrackSelection;
     use std::error::Error as _;
 
     struct DummyBackend;
@@ -286,12 +840,8 @@ mod tests {
 
     fn default_opts(output_type: OutputType) -> Opts {
         Opts {
-            model_key: None,
-            enable_translate_to_english: false,
-            enable_voice_activity_detection: false,
-            language: None,
             output_type,
-            incremental_min_window_seconds: 1,
+            ..Opts::default()
         }
     }
 
@@ -384,18 +934,117 @@ mod tests {
         }
     }
 
+    /// Reports how many samples each full-buffer pass received.
+    struct SampleCountBackend;
+
+    impl Backend for SampleCountBackend {
+        type Stream<'a>
+            = FinishErrStream
+        where
+            Self: 'a;
+
+        fn transcribe_full(
+            &self,
+            _opts: &Opts,
+            encoder: &mut dyn SegmentEncoder,
+            samples: &[f32],
+        ) -> Result<()> {
+            encoder.write_segment(&crate::segments::Segment {
+                start_seconds: 0.0,
+                end_seconds: samples.len() as f32 / TARGET_SAMPLE_RATE as f32,
+                text: samples.len().to_string(),
+                ..Segment::default()
+            })
+        }
+
+        fn create_stream<'a>(
+            &'a self,
+            _opts: &'a Opts,
+            _encoder: &'a mut dyn SegmentEncoder,
+        ) -> Result<Self::Stream<'a>> {
+            Ok(FinishErrStream)
+        }
+    }
+
+    #[test]
+    fn full_mode_runs_a_single_full_buffer_pass() -> anyhow::Result<()> {
+        let scribble = Scribble::with_backend(SampleCountBackend);
+        let mut opts = default_opts(OutputType::Json);
+        opts.transcription_mode = TranscriptionMode::Full { max_seconds: 60 };
+        let input = std::fs::File::open("tests/fixtures/jfk.wav")?;
+        let mut out = Vec::new();
+
+        scribble.transcribe(input, &mut out, &opts)?;
+
+        let parsed: serde_json::Value = serde_json::from_slice(&out)?;
+        let segments = parsed.as_array().expect("expected JSON array");
+        assert_eq!(segments.len(), 1);
+        let sample_count: usize = segments[0]["text"].as_str().unwrap_or_default().parse()?;
+        // jfk.wav is ~8.5s of 16 kHz audio.
+        assert!((130_000..=140_000).contains(&sample_count));
+        Ok(())
+    }
+
+    #[test]
+    fn full_mode_rejects_inputs_longer_than_max_seconds() -> anyhow::Result<()> {
+        let scribble = Scribble::with_backend(SampleCountBackend);
+        let mut opts = default_opts(OutputType::Json);
+        opts.transcription_mode = TranscriptionMode::Full { max_seconds: 1 };
+        let input = std::fs::File::open("tests/fixtures/jfk.wav")?;
+
+        let err = scribble
+            .transcribe_with_encoder(input, &opts, &mut NoopEncoder)
+            .unwrap_err();
+        assert!(matches!(err, crate::Error::InvalidInput { .. }));
+        assert!(err.to_string().contains("longer than 1s"));
+        Ok(())
+    }
+
     #[test]
     fn get_samples_rx_errors_when_vad_enabled_but_missing_processor() {
         let mut opts = default_opts(OutputType::Json);
         opts.enable_voice_activity_detection = true;
 
-        let input = std::io::Cursor::new(Vec::<u8>::new());
-        let err = Scribble::<DummyBackend>::get_samples_rx(input, &opts, None)
+        let input = DecodeInput::Stream(Box::new(std::io::Cursor::new(Vec::<u8>::new())));
+        let control = DecodeControl::new(&opts);
+        let err = Scribble::<DummyBackend>::get_samples_rx(input, &opts, None, &control, None)
             .err()
             .expect("expected get_samples_rx() to error");
         assert!(err.to_string().contains("VAD failed to initialize"));
     }
 
+    #[test]
+    fn parallel_chunks_rejects_zero_workers() {
+        let scribble = Scribble::with_backend(DummyBackend);
+        let mut opts = default_opts(OutputType::Json);
+        opts.transcription_mode = TranscriptionMode::ParallelChunks {
+            workers: 0,
+            chunk_seconds: 60,
+        };
+        let input = std::io::Cursor::new(Vec::<u8>::new());
+
+        let err = scribble
+            .transcribe_with_encoder(input, &opts, &mut NoopEncoder)
+            .unwrap_err();
+        assert!(matches!(err, crate::Error::InvalidInput { .. }));
+    }
+
+    #[test]
+    fn parallel_chunks_requires_vad_model() {
+        let scribble = Scribble::with_backend(DummyBackend);
+        let mut opts = default_opts(OutputType::Json);
+        opts.transcription_mode = TranscriptionMode::ParallelChunks {
+            workers: 2,
+            chunk_seconds: 60,
+        };
+        let input = std::io::Cursor::new(Vec::<u8>::new());
+
+        let err = scribble
+            .transcribe_with_encoder(input, &opts, &mut NoopEncoder)
+            .unwrap_err();
+        assert!(err.to_string().contains("needs a VAD model"));
+    }
+
     #[test]
     fn transcribe_with_encoder_surfaces_decoder_error_when_finish_ok() {
         let scribble = Scribble::with_backend(DummyBackend);
@@ -439,6 +1088,51 @@ mod tests {
         assert!(source.contains("finish failed"));
     }
 
+    #[test]
+    fn transcribe_with_encoder_returns_cancelled_when_token_is_cancelled() -> anyhow::Result<()> {
+        let scribble = Scribble::with_backend(DummyBackend);
+        let token = crate::CancellationToken::new();
+        token.cancel();
+        let mut opts = default_opts(OutputType::Json);
+        opts.cancellation_token = Some(token);
+        let input = std::fs::File::open("tests/fixtures/jfk.wav")?;
+        let mut encoder = NoopEncoder;
+
+        let err = scribble
+            .transcribe_with_encoder(input, &opts, &mut encoder)
+            .unwrap_err();
+        assert!(matches!(err, crate::Error::Cancelled));
+        Ok(())
+    }
+
+    #[test]
+    fn transcribe_with_encoder_reports_progress() -> anyhow::Result<()> {
+        let scribble = Scribble::with_backend(DummyBackend);
+        let last = Arc::new(std::sync::Mutex::new(crate::Progress::default()));
+        let sink = Arc::clone(&last);
+        let mut opts = default_opts(OutputType::Json);
+        opts.progress_listener = Some(Arc::new(move |p: &crate::Progress| {
+            *sink.lock().expect("lock") = *p;
+        }));
+        let input = std::fs::File::open("tests/fixtures/jfk.wav")?;
+        let mut encoder = NoopEncoder;
+
+        scribble.transcribe_with_encoder(input, &opts, &mut encoder)?;
+
+        let last = *last.lock().expect("lock");
+        let total = last.total_seconds.expect("WAV reports its duration");
+        assert!((total - 8.5).abs() < 0.1, "unexpected total: {total}");
+        assert!((last.decoded_seconds - total).abs() < 0.1);
+        assert_eq!(last.transcribed_seconds, last.decoded_seconds);
+        Ok(())
+    }
+
+    #[test]
+    fn cancelled_error_survives_anyhow_round_trip() {
+        let err = anyhow::Error::from(crate::Error::Cancelled).context("while running whisper");
+        assert!(matches!(crate::Error::from(err), crate::Error::Cancelled));
+    }
+
     struct PanicRead;
 
     impl Read for PanicRead {
@@ -498,4 +1192,428 @@ mod tests {
         );
         Ok(())
     }
+
+    /// A backend whose only model is English-only and cannot translate.
+    struct EnglishOnlyBackend;
+
+    impl Backend for EnglishOnlyBackend {
+        type Stream<'a>
+            = DummyStream
+        where
+            Self: 'a;
+
+        fn transcribe_full(
+            &self,
+            _opts: &Opts,
+            _encoder: &mut dyn SegmentEncoder,
+            _samples: &[f32],
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        fn create_stream<'a>(
+            &'a self,
+            _opts: &'a Opts,
+            _encoder: &'a mut dyn SegmentEncoder,
+        ) -> Result<Self::Stream<'a>> {
+            Ok(DummyStream)
+        }
+
+        fn capabilities(&self, _model_key: Option<&str>) -> crate::Capabilities {
+            crate::Capabilities {
+                translation: false,
+                languages: Some(vec!["en".to_owned()]),
+                ..crate::Capabilities::default()
+            }
+        }
+    }
+
+    #[test]
+    fn transcribe_rejects_unsupported_opts_before_writing_output() {
+        let scribble = Scribble::with_backend(EnglishOnlyBackend);
+        let mut opts = default_opts(OutputType::Json);
+        opts.enable_translate_to_english = true;
+        let input = std::io::Cursor::new(Vec::<u8>::new());
+        let mut out = Vec::new();
+
+        let err = scribble.transcribe(input, &mut out, &opts).unwrap_err();
+
+        assert!(matches!(err, crate::Error::InvalidInput { .. }));
+        assert!(out.is_empty());
+    }
+
+    /// Asks to stop after the first chunk, counting the chunks it is fed.
+    #[derive(Default)]
+    struct StopEarlyBackend {
+        chunks: std::cell::Cell<usize>,
+    }
+
+    struct StopEarlyStream<'a>(&'a StopEarlyBackend);
+
+    impl BackendStream for StopEarlyStream<'_> {
+        fn on_samples(&mut self, _samples_16k_mono: &[f32]) -> Result<bool> {
+            self.0.chunks.set(self.0.chunks.get() + 1);
+            Ok(false)
+        }
+
+        fn finish(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Backend for StopEarlyBackend {
+        type Stream<'a>
+            = StopEarlyStream<'a>
+        where
+            Self: 'a;
+
+        fn transcribe_full(
+            &self,
+            _opts: &Opts,
+            _encoder: &mut dyn SegmentEncoder,
+            _samples: &[f32],
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        fn create_stream<'a>(
+            &'a self,
+            _opts: &'a Opts,
+            _encoder: &'a mut dyn SegmentEncoder,
+        ) -> Result<Self::Stream<'a>> {
+            Ok(StopEarlyStream(self))
+        }
+    }
+
+    #[test]
+    fn streaming_honors_backend_stop_request() -> anyhow::Result<()> {
+        let scribble = Scribble::with_backend(StopEarlyBackend::default());
+        let opts = default_opts(OutputType::Json);
+        let input = std::fs::File::open("tests/fixtures/jfk.wav")?;
+
+        scribble.transcribe_with_encoder(input, &opts, &mut NoopEncoder)?;
+
+        assert_eq!(scribble.backend().chunks.get(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn max_audio_seconds_truncates_input() -> anyhow::Result<()> {
+        let scribble = Scribble::with_backend(SampleCountBackend);
+        let mut opts = default_opts(OutputType::Json);
+        opts.transcription_mode = TranscriptionMode::Full { max_seconds: 60 };
+        opts.max_audio_seconds = Some(2);
+        let input = std::fs::File::open("tests/fixtures/jfk.wav")?;
+        let mut out = Vec::new();
+
+        scribble.transcribe(input, &mut out, &opts)?;
+
+        let parsed: serde_json::Value = serde_json::from_slice(&out)?;
+        assert_eq!(parsed[0]["text"], "32000");
+        Ok(())
+    }
+
+    #[test]
+    fn max_wall_clock_seconds_stops_reading_input() -> anyhow::Result<()> {
+        let scribble = Scribble::with_backend(SampleCountBackend);
+        let mut opts = default_opts(OutputType::Json);
+        opts.transcription_mode = TranscriptionMode::Full { max_seconds: 60 };
+        opts.max_wall_clock_seconds = Some(0);
+        let input = std::fs::File::open("tests/fixtures/jfk.wav")?;
+        let mut out = Vec::new();
+
+        scribble.transcribe(input, &mut out, &opts)?;
+
+        let parsed: serde_json::Value = serde_json::from_slice(&out)?;
+        assert_eq!(parsed[0]["text"], "0");
+        Ok(())
+    }
+
+    /// Yields one second of raw silence, then blocks forever like an idle stdin.
+    struct StalledReader {
+        remaining: usize,
+    }
+
+    impl Read for StalledReader {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.remaining == 0 {
+                loop {
+                    std::thread::park();
+                }
+            }
+            let n = buf.len().min(self.remaining);
+            buf[..n].fill(0);
+            self.remaining -= n;
+            Ok(n)
+        }
+    }
+
+    fn stalled_input_opts(mode: TranscriptionMode) -> anyhow::Result<Opts> {
+        let mut opts = default_opts(OutputType::Json);
+        opts.transcription_mode = mode;
+        opts.raw_input_format = Some("s16le:16000:1".parse()?);
+        opts.max_wall_clock_seconds = Some(1);
+        Ok(opts)
+    }
+
+    #[test]
+    fn max_wall_clock_seconds_fires_while_streaming_input_is_stalled() -> anyhow::Result<()> {
+        let scribble = Scribble::with_backend(DummyBackend);
+        let opts = stalled_input_opts(TranscriptionMode::Streaming)?;
+        let started = Instant::now();
+
+        scribble.transcribe_with_encoder(
+            StalledReader { remaining: 32_000 },
+            &opts,
+            &mut NoopEncoder,
+        )?;
+
+        assert!(started.elapsed() < Duration::from_secs(10));
+        Ok(())
+    }
+
+    #[test]
+    fn max_wall_clock_seconds_fires_while_copying_a_stalled_input_for_per_channel_passes()
+    -> anyhow::Result<()> {
+        let scribble = Scribble::with_backend(DummyBackend);
+        let mut opts = stalled_input_opts(TranscriptionMode::Full { max_seconds: 60 })?;
+        opts.enable_per_channel_transcription = true;
+        let started = Instant::now();
+
+        scribble.transcribe_with_encoder(
+            StalledReader { remaining: 32_000 },
+            &opts,
+            &mut NoopEncoder,
+        )?;
+
+        assert!(started.elapsed() < Duration::from_secs(10));
+        Ok(())
+    }
+
+    #[test]
+    fn max_wall_clock_seconds_keeps_audio_decoded_before_a_stall() -> anyhow::Result<()> {
+        let scribble = Scribble::with_backend(SampleCountBackend);
+        let opts = stalled_input_opts(TranscriptionMode::Full { max_seconds: 60 })?;
+        let started = Instant::now();
+        let mut out = Vec::new();
+
+        scribble.transcribe(StalledReader { remaining: 32_000 }, &mut out, &opts)?;
+
+        assert!(started.elapsed() < Duration::from_secs(10));
+        let parsed: serde_json::Value = serde_json::from_slice(&out)?;
+        assert_eq!(parsed[0]["text"], "16000");
+        Ok(())
+    }
+
+    #[test]
+    fn time_range_trims_input_and_keeps_original_timestamps() -> anyhow::Result<()> {
+        let scribble = Scribble::with_backend(SampleCountBackend);
+        let mut opts = default_opts(OutputType::Json);
+        opts.transcription_mode = TranscriptionMode::Full { max_seconds: 60 };
+        opts.start_seconds = Some(2.0);
+        opts.end_seconds = Some(5.0);
+        let input = std::fs::File::open("tests/fixtures/jfk.wav")?;
+        let mut out = Vec::new();
+
+        scribble.transcribe(input, &mut out, &opts)?;
+
+        let parsed: serde_json::Value = serde_json::from_slice(&out)?;
+        assert_eq!(parsed[0]["text"], "48000");
+        assert_eq!(parsed[0]["start_seconds"], 2.0);
+        assert_eq!(parsed[0]["end_seconds"], 5.0);
+        Ok(())
+    }
+
+    #[test]
+    fn transcribe_seekable_decodes_trailing_moov_and_seeks_to_start() -> anyhow::Result<()> {
+        let scribble = Scribble::with_backend(SampleCountBackend);
+        let mut opts = default_opts(OutputType::Json);
+        opts.transcription_mode = TranscriptionMode::Full { max_seconds: 60 };
+        opts.start_seconds = Some(0.5);
+        // 2s of audio with the `moov` box after `mdat`.
+        let input = std::fs::File::open("tests/fixtures/tone-moov-at-end.mp4")?;
+        let mut out = Vec::new();
+
+        scribble.transcribe_seekable(input, &mut out, &opts)?;
+
+        let parsed: serde_json::Value = serde_json::from_slice(&out)?;
+        assert_eq!(parsed[0]["text"], "24000");
+        assert_eq!(parsed[0]["start_seconds"], 0.5);
+        Ok(())
+    }
+
+    #[test]
+    fn time_range_must_be_ordered() {
+        let scribble = Scribble::with_backend(DummyBackend);
+        let mut opts = default_opts(OutputType::Json);
+        opts.start_seconds = Some(5.0);
+        opts.end_seconds = Some(2.0);
+
+        let err = scribble.validate_opts(&opts).unwrap_err();
+        assert!(matches!(err, crate::Error::InvalidInput { .. }));
+
+        let mut out = Vec::new();
+        let input = std::io::Cursor::new(Vec::<u8>::new());
+        let err = scribble.transcribe(input, &mut out, &opts).unwrap_err();
+        assert!(matches!(err, crate::Error::InvalidInput { .. }));
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn time_range_must_be_non_negative_numbers() {
+        let scribble = Scribble::with_backend(DummyBackend);
+        for start in [f32::NAN, -1.0, f32::INFINITY] {
+            let mut opts = default_opts(OutputType::Json);
+            opts.start_seconds = Some(start);
+
+            let err = scribble.validate_opts(&opts).unwrap_err();
+            assert!(matches!(err, crate::Error::InvalidInput { .. }), "{start}");
+        }
+    }
+
+    /// Emits one segment starting where the audio first gets loud.
+    struct OnsetBackend;
+
+    impl Backend for OnsetBackend {
+        type Stream<'a>
+            = FinishErrStream
+        where
+            Self: 'a;
+
+        fn transcribe_full(
+            &self,
+            _opts: &Opts,
+            encoder: &mut dyn SegmentEncoder,
+            samples: &[f32],
+        ) -> Result<()> {
+            let Some(onset) = samples.iter().position(|s| s.abs() > 0.1) else {
+                return Ok(());
+            };
+            encoder.write_segment(&crate::segments::Segment {
+                start_seconds: onset as f32 / TARGET_SAMPLE_RATE as f32,
+                end_seconds: samples.len() as f32 / TARGET_SAMPLE_RATE as f32,
+                text: "speech".to_owned(),
+                ..Segment::default()
+            })
+        }
+
+        fn create_stream<'a>(
+            &'a self,
+            _opts: &'a Opts,
+            _encoder: &'a mut dyn SegmentEncoder,
+        ) -> Result<Self::Stream<'a>> {
+            Ok(FinishErrStream)
+        }
+    }
+
+    /// 2s of stereo audio: the left channel gets loud at 1s, the right one at 0.25s.
+    fn stereo_onsets_wav() -> anyhow::Result<Vec<u8>> {
+        let spec = hound::WavSpec {
+            channels: 2,
+            sample_rate: TARGET_SAMPLE_RATE,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut wav = Vec::new();
+        {
+            let mut writer = hound::WavWriter::new(std::io::Cursor::new(&mut wav), spec)?;
+            for i in 0..2 * TARGET_SAMPLE_RATE as usize {
+                let loud_from = |seconds: f32| i as f32 >= seconds * TARGET_SAMPLE_RATE as f32;
+                writer.write_sample(if loud_from(1.0) { 16384i16 } else { 0 })?;
+                writer.write_sample(if loud_from(0.25) { 16384i16 } else { 0 })?;
+            }
+            writer.finalize()?;
+        }
+        Ok(wav)
+    }
+
+    #[test]
+    fn per_channel_transcription_tags_and_orders_segments() -> anyhow::Result<()> {
+        let wav = stereo_onsets_wav()?;
+        let scribble = Scribble::with_backend(OnsetBackend);
+        let mut opts = default_opts(OutputType::Json);
+        opts.transcription_mode = TranscriptionMode::Full { max_seconds: 60 };
+        opts.enable_per_channel_transcription = true;
+        let mut out = Vec::new();
+
+        scribble.transcribe(std::io::Cursor::new(wav), &mut out, &opts)?;
+
+        let parsed: serde_json::Value = serde_json::from_slice(&out)?;
+        assert_eq!(parsed.as_array().map(Vec::len), Some(2));
+        assert_eq!(parsed[0]["channel"], 1);
+        assert_eq!(parsed[0]["start_seconds"], 0.25);
+        assert_eq!(parsed[1]["channel"], 0);
+        assert_eq!(parsed[1]["start_seconds"], 1.0);
+        Ok(())
+    }
+
+    #[test]
+    fn per_channel_passes_share_the_wall_clock_budget() {
+        let mut opts = default_opts(OutputType::Json);
+        opts.max_wall_clock_seconds = Some(10);
+        let control = DecodeControl::new(&opts);
+
+        let first = control.for_channel(0, 2).deadline.expect("deadline");
+        let last = control.for_channel(1, 1).deadline.expect("deadline");
+
+        let first_budget = first.saturating_duration_since(Instant::now());
+        assert!(first_budget <= Duration::from_secs(5), "{first_budget:?}");
+        assert!(first_budget > Duration::from_secs(4), "{first_budget:?}");
+        assert!(last > first);
+    }
+
+    #[test]
+    fn unrepresentable_wall_clock_budget_means_no_deadline() {
+        let mut opts = default_opts(OutputType::Json);
+        opts.max_wall_clock_seconds = Some(usize::MAX);
+        let control = DecodeControl::new(&opts);
+
+        assert!(control.deadline.is_none());
+        assert!(!control.should_stop());
+    }
+
+    #[test]
+    fn per_channel_progress_spans_every_pass() -> anyhow::Result<()> {
+        let scribble = Scribble::with_backend(OnsetBackend);
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        let mut opts = default_opts(OutputType::Json);
+        opts.transcription_mode = TranscriptionMode::Full { max_seconds: 60 };
+        opts.enable_per_channel_transcription = true;
+        opts.progress_listener = Some(Arc::new(move |p: &crate::Progress| {
+            sink.lock().expect("lock").push(*p);
+        }));
+        let input = std::io::Cursor::new(stereo_onsets_wav()?);
+
+        scribble.transcribe_with_encoder(input, &opts, &mut NoopEncoder)?;
+
+        let seen = seen.lock().expect("lock");
+        let last = *seen.last().expect("snapshot");
+        let near = |seconds: f64, expected: f64| (seconds - expected).abs() < 0.01;
+        // Two 2s passes, one per channel.
+        assert_eq!(last.total_seconds, Some(4.0));
+        assert!(near(last.decoded_seconds, 4.0), "{last:?}");
+        assert_eq!(last.transcribed_seconds, last.decoded_seconds);
+        // The first pass's segment ends at 2s; the run is not reported done before the second.
+        assert!(seen.iter().any(|p| near(p.transcribed_seconds, 2.0)));
+        assert!(
+            seen.windows(2)
+                .all(|w| w[0].transcribed_seconds <= w[1].transcribed_seconds)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn per_channel_transcription_rejects_mixed_tracks() {
+        let scribble = Scribble::with_backend(DummyBackend);
+        let mut opts = default_opts(OutputType::Json);
+        opts.enable_per_channel_transcription = true;
+        opts.audio_track = TrackSelection::All;
+        let input = std::io::Cursor::new(Vec::<u8>::new());
+
+        let err = scribble
+            .transcribe_with_encoder(input, &opts, &mut NoopEncoder)
+            .unwrap_err();
+        assert!(matches!(err, crate::Error::InvalidInput { .. }));
+    }
 }
@@ -7,7 +7,7 @@ use crate::segments::Segment;
 /// (JSON, VTT, plain text, ...).
 ///
 /// Lifecycle:
-/// - Call `write_segment` zero or more times.
+/// - Call `write_segment` (and optionally `write_partial`) zero or more times.
 /// - Call `close` exactly once when done.
 /// - Implementations should treat `close()` as idempotent (safe to call multiple times).
 ///
@@ -19,8 +19,61 @@ pub trait SegmentEncoder {
     /// Encode and write a single segment.
     fn write_segment(&mut self, seg: &Segment) -> Result<()>;
 
+    /// Receive the current provisional (unstable) tail of a streaming transcription.
+    ///
+    /// Called after each streaming inference pass when `Opts::enable_partial_results` is set.
+    /// `segments` replaces any previously reported partial tail; text in it may still change
+    /// before it is committed via `write_segment`.
+    ///
+    /// Defaults to a no-op so formats without a notion of interim results (JSON arrays,
+    /// subtitles) ignore them.
+    fn write_partial(&mut self, _segments: &[Segment]) -> Result<()> {
+        Ok(())
+    }
+
     /// Finalize the encoded output and flush any buffered data.
     ///
     /// Prefer `close()` to be idempotent so callers can safely call it in cleanup paths.
     fn close(&mut self) -> Result<()>;
 }
+
+/// Adapter that shifts every segment by a fixed offset before forwarding it.
+///
+/// Used when a backend transcribes a slice of a longer buffer, so emitted timestamps stay
+/// relative to the start of the whole input.
+pub(crate) struct OffsetEncoder<'a> {
+    inner: &'a mut dyn SegmentEncoder,
+    offset_seconds: f32,
+}
+
+impl<'a> OffsetEncoder<'a> {
+    pub(crate) fn new(inner: &'a mut dyn SegmentEncoder, offset_seconds: f32) -> Self {
+        Self {
+            inner,
+            offset_seconds,
+        }
+    }
+
+    fn shifted(&self, seg: &Segment) -> Segment {
+        let mut seg = seg.clone();
+        seg.shift_by(self.offset_seconds);
+        seg
+    }
+}
+
+impl SegmentEncoder for OffsetEncoder<'_> {
+    fn write_segment(&mut self, seg: &Segment) -> Result<()> {
+        let seg = self.shifted(seg);
+        self.inner.write_segment(&seg)
+    }
+
+    fn write_partial(&mut self, segments: &[Segment]) -> Result<()> {
+        let segments: Vec<Segment> = segments.iter().map(|seg| self.shifted(seg)).collect();
+        self.inner.write_partial(&segments)
+    }
+
+    /// The wrapped encoder's lifecycle belongs to the caller; closing the adapter is a no-op.
+    fn close(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
@@ -1,5 +1,5 @@
-use crate::token::Token;
-use serde::Serialize;
+use crate::token::{Token, Word};
+use serde::{Deserialize, Serialize};
 
 /// A single transcription segment produced by an ASR backend.
 ///
@@ -7,7 +7,10 @@ use serde::Serialize;
 /// - timestamps are in seconds (f32 is sufficient for typical subtitle timing)
 /// - `text` is the raw segment text returned by the backend
 /// - `language_code` is included for forward compatibility (see notes below)
-#[derive(Debug, Serialize, Clone)]
+///
+/// `Segment::default()` is an empty segment at `0.0` with no language, text or scores; build
+/// segments from it with `..Segment::default()`.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
 pub struct Segment {
     pub start_seconds: f32,
     pub end_seconds: f32,
@@ -17,8 +20,14 @@ pub struct Segment {
     ///
     /// Includes token-level timing and probabilities so consumers can build detailed overlays or
     /// custom renderers without re-tokenizing.
+    ///
+    /// Only text tokens are kept unless `Opts::enable_non_text_tokens` is set, in which case
+    /// timestamp and control tokens are included too; check [`Token::kind`] to tell them apart.
     pub tokens: Vec<Token>,
 
+    /// The segment's text tokens grouped into whole words, with combined timing and probability.
+    pub words: Vec<Word>,
+
     /// Language of the segment as a short code (e.g. "en", "es").
     ///
     /// Defaults to a placeholder until per-segment language detection is implemented.
@@ -29,4 +38,95 @@ pub struct Segment {
     /// Populated from `WhisperSegment::next_segment_speaker_turn()` so downstream
     /// encoders/UIs can insert speaker breaks without re-deriving this signal.
     pub next_speaker_turn: bool,
+
+    /// Mean natural-log probability of the segment's text tokens (always `<= 0.0`).
+    ///
+    /// Closer to `0.0` means the backend was more certain. `0.0` when the segment has no text
+    /// tokens.
+    pub avg_logprob: f32,
+
+    /// Probability (0.0–1.0) that the segment contains no speech, as estimated by the backend.
+    ///
+    /// High values on segments with text usually indicate hallucinated output over silence or
+    /// music. `0.0` when the backend does not report it.
+    pub no_speech_probability: f32,
+
+    /// Overall confidence (0.0–1.0) derived from `avg_logprob` and `no_speech_probability`.
+    ///
+    /// Computed as `exp(avg_logprob) * (1 - no_speech_probability)`: the geometric mean token
+    /// probability, discounted by the chance the segment is not speech at all. `0.0` when the
+    /// segment has no text tokens.
+    pub confidence: f32,
+
+    /// True if the segment was produced by the secondary backend of a
+    /// [`crate::FallbackBackend`] after the primary failed.
+    ///
+    /// Defaults to `false` when missing, so output from older versions still deserializes.
+    #[serde(default)]
+    pub from_fallback: bool,
+
+    /// Source channel (zero-based) the segment was transcribed from when
+    /// `Opts::enable_per_channel_transcription` is set; `None` for downmixed audio.
+    ///
+    /// In call recordings with one speaker per channel, this identifies the speaker.
+    pub channel: Option<usize>,
+}
+
+impl Segment {
+    /// Derive [`Segment::confidence`] from the other two scores.
+    pub(crate) fn confidence_from(
+        avg_logprob: f32,
+        no_speech_probability: f32,
+        has_text: bool,
+    ) -> f32 {
+        if !has_text {
+            return 0.0;
+        }
+        (avg_logprob.exp() * (1.0 - no_speech_probability)).clamp(0.0, 1.0)
+    }
+
+    /// Shift the segment (and its tokens) later in time by `offset_seconds`.
+    ///
+    /// Backends produce timestamps relative to the buffer they were given; callers that feed
+    /// a slice of a longer input use this to map them back onto the input's timeline.
+    pub(crate) fn shift_by(&mut self, offset_seconds: f32) {
+        self.start_seconds += offset_seconds;
+        self.end_seconds += offset_seconds;
+        for token in &mut self.tokens {
+            token.start_seconds += offset_seconds;
+            token.end_seconds += offset_seconds;
+        }
+        for word in &mut self.words {
+            word.start_seconds += offset_seconds;
+            word.end_seconds += offset_seconds;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn confidence_combines_token_and_speech_probabilities() {
+        let confidence = Segment::confidence_from(0.5_f32.ln(), 0.2, true);
+        assert!((confidence - 0.4).abs() < 1e-6);
+    }
+
+    #[test]
+    fn confidence_is_zero_without_text() {
+        assert_eq!(Segment::confidence_from(0.0, 0.0, false), 0.0);
+    }
+
+    #[test]
+    fn segments_without_from_fallback_still_deserialize() -> serde_json::Result<()> {
+        let segment: Segment = serde_json::from_str(
+            r#"{"start_seconds":0.0,"end_seconds":1.0,"text":"hi","tokens":[],"words":[],
+                "language_code":"en","next_speaker_turn":false,"avg_logprob":0.0,
+                "no_speech_probability":0.0,"confidence":0.0}"#,
+        )?;
+        assert!(!segment.from_fallback);
+        assert_eq!(segment.channel, None);
+        Ok(())
+    }
 }
@@ -1,7 +1,30 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+
+/// What a token represents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenKind {
+    /// Transcribed text (a word or a piece of one).
+    Text,
+    /// A timestamp marker (whisper renders these like `[_TT_50]`).
+    Timestamp,
+    /// Any other control token, such as `[_BEG_]` or `[_SOT_]`.
+    Special,
+}
+
+impl TokenKind {
+    /// Classify a token from its rendered text.
+    pub(crate) fn classify(text: &str) -> Self {
+        match text.strip_prefix("[_").and_then(|t| t.strip_suffix(']')) {
+            Some(inner) if inner.starts_with("TT_") => Self::Timestamp,
+            Some(_) => Self::Special,
+            None => Self::Text,
+        }
+    }
+}
 
 /// A single token produced by an ASR backend.
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Token {
     /// Start time in seconds (whisper returns centiseconds).
     pub start_seconds: f32,
@@ -11,6 +34,64 @@ pub struct Token {
     pub text: String,
     /// Probability assigned to this token.
     pub probability: f32,
+    /// Whether this is text, a timestamp marker, or another control token.
+    pub kind: TokenKind,
+}
+
+impl Token {
+    pub(crate) fn is_text(&self) -> bool {
+        self.kind == TokenKind::Text
+    }
+}
+
+/// A whole word assembled from one or more sub-word text tokens.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Word {
+    /// Start of the first token in the word, in seconds.
+    pub start_seconds: f32,
+    /// End of the last token in the word, in seconds.
+    pub end_seconds: f32,
+    /// Word text without surrounding whitespace; trailing punctuation stays attached.
+    pub text: String,
+    /// Mean probability of the tokens that make up the word.
+    pub probability: f32,
+}
+
+/// Group sub-word text tokens into words.
+///
+/// BPE tokenizers mark the start of a new word with leading whitespace, so a token without it
+/// continues the previous word. Non-text tokens are ignored.
+pub(crate) fn group_words(tokens: &[Token]) -> Vec<Word> {
+    let mut words: Vec<Word> = Vec::new();
+    let mut token_counts: Vec<usize> = Vec::new();
+
+    for token in tokens.iter().filter(|token| token.is_text()) {
+        let starts_word = token.text.starts_with(char::is_whitespace);
+        match words.last_mut() {
+            Some(word) if !starts_word => {
+                word.end_seconds = token.end_seconds;
+                word.text.push_str(&token.text);
+                word.probability += token.probability;
+                *token_counts.last_mut().expect("one count per word") += 1;
+            }
+            _ => {
+                words.push(Word {
+                    start_seconds: token.start_seconds,
+                    end_seconds: token.end_seconds,
+                    text: token.text.clone(),
+                    probability: token.probability,
+                });
+                token_counts.push(1);
+            }
+        }
+    }
+
+    for (word, count) in words.iter_mut().zip(token_counts) {
+        word.text = word.text.trim().to_owned();
+        word.probability /= count as f32;
+    }
+    words.retain(|word| !word.text.is_empty());
+    words
 }
 
 pub(crate) fn centiseconds_to_seconds(value: i64) -> f32 {
@@ -33,4 +114,43 @@ mod tests {
         assert_eq!(centiseconds_to_seconds(1), 0.01);
         assert_eq!(centiseconds_to_seconds(250), 2.5);
     }
+
+    fn token(text: &str, start: f32, end: f32, probability: f32) -> Token {
+        Token {
+            start_seconds: start,
+            end_seconds: end,
+            text: text.to_owned(),
+            probability,
+            kind: TokenKind::classify(text),
+        }
+    }
+
+    #[test]
+    fn classify_distinguishes_text_timestamps_and_control_tokens() {
+        assert_eq!(TokenKind::classify(" hello"), TokenKind::Text);
+        assert_eq!(TokenKind::classify("[_TT_50]"), TokenKind::Timestamp);
+        assert_eq!(TokenKind::classify("[_BEG_]"), TokenKind::Special);
+        assert_eq!(TokenKind::classify("[_SOT_]"), TokenKind::Special);
+        assert_eq!(TokenKind::classify("[music]"), TokenKind::Text);
+    }
+
+    #[test]
+    fn group_words_joins_sub_word_tokens() {
+        let tokens = [
+            token("[_BEG_]", 0.0, 0.0, 1.0),
+            token(" trans", 0.0, 0.2, 0.8),
+            token("cription", 0.2, 0.5, 0.6),
+            token(" works", 0.5, 0.9, 0.9),
+            token(".", 0.9, 1.0, 0.5),
+            token("[_TT_100]", 1.0, 1.0, 1.0),
+        ];
+
+        let words = group_words(&tokens);
+        assert_eq!(words.len(), 2);
+        assert_eq!(words[0].text, "transcription");
+        assert_eq!((words[0].start_seconds, words[0].end_seconds), (0.0, 0.5));
+        assert!((words[0].probability - 0.7).abs() < 1e-6);
+        assert_eq!(words[1].text, "works.");
+        assert_eq!((words[1].start_seconds, words[1].end_seconds), (0.5, 1.0));
+    }
 }
@@ -0,0 +1,82 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Which audio track(s) of a multi-track container to transcribe.
+///
+/// Only tracks Scribble can decode count: indexes are positions among the container's audio
+/// tracks, in container order. When a selection matches nothing, the error lists the available
+/// tracks with their index, language, codec, sample rate, and channel count.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum TrackSelection {
+    /// The first audio track.
+    #[default]
+    First,
+
+    /// The audio track at this zero-based index.
+    Index(usize),
+
+    /// The first audio track whose language tag matches (case-insensitively), e.g. `"eng"`.
+    ///
+    /// Containers usually tag tracks with three-letter ISO 639-2 codes.
+    Language(String),
+
+    /// Every audio track, mixed down into one mono stream and transcribed as one.
+    ///
+    /// Useful for recordings with one track per speaker, who mostly take turns. Tracks that
+    /// carry simultaneous speech, such as dubbed languages, overlap in the mix and transcribe
+    /// poorly; select each of those on its own instead. Segments do not say which track they
+    /// came from.
+    All,
+}
+
+impl FromStr for TrackSelection {
+    type Err = crate::Error;
+
+    /// Parse `"first"`, `"all"`, an index (e.g. `"1"`), or a language tag (e.g. `"eng"`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(crate::Error::invalid_input(
+                "track selection must be an index, a language tag, \"first\", or \"all\"",
+            ));
+        }
+
+        Ok(match s.to_ascii_lowercase().as_str() {
+            "first" => Self::First,
+            "all" => Self::All,
+            _ => match s.parse() {
+                Ok(index) => Self::Index(index),
+                Err(_) => Self::Language(s.to_owned()),
+            },
+        })
+    }
+}
+
+impl fmt::Display for TrackSelection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::First => f.write_str("first"),
+            Self::Index(index) => write!(f, "{index}"),
+            Self::Language(language) => f.write_str(language),
+            Self::All => f.write_str("all"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_keywords_indexes_and_languages() -> anyhow::Result<()> {
+        assert_eq!("all".parse::<TrackSelection>()?, TrackSelection::All);
+        assert_eq!("First".parse::<TrackSelection>()?, TrackSelection::First);
+        assert_eq!("2".parse::<TrackSelection>()?, TrackSelection::Index(2));
+        assert_eq!(
+            " eng ".parse::<TrackSelection>()?,
+            TrackSelection::Language("eng".to_owned())
+        );
+        assert!("".parse::<TrackSelection>().is_err());
+        Ok(())
+    }
+}
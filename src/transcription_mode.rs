@@ -0,0 +1,31 @@
+/// How `Scribble` feeds decoded audio to the backend.
+///
+/// Streaming works for any input, including live sources, and bounds memory. Buffered modes
+/// trade that for throughput on finite inputs such as long recordings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranscriptionMode {
+    /// Feed audio to the backend incrementally as it is decoded.
+    Streaming,
+
+    /// Decode the whole input, apply VAD (when enabled), and run a single full-buffer pass.
+    ///
+    /// Avoids the windowing artifacts of streaming (words cut at window edges, repeated
+    /// re-decoding) at the cost of holding the decoded audio in memory and producing no output
+    /// until decoding finishes. Inputs longer than `max_seconds` are rejected with
+    /// [`crate::Error::InvalidInput`] rather than exhausting memory.
+    Full { max_seconds: usize },
+
+    /// Decode the whole input, split it at VAD-detected silences, and transcribe the chunks
+    /// concurrently.
+    ///
+    /// Chunks are grown to roughly `chunk_seconds` before a cut is placed in the middle of the
+    /// next silence, so no word is split across chunks. Up to `workers` chunks are transcribed at
+    /// once; segments are still emitted in order with timestamps relative to the whole input.
+    ///
+    /// Requires a VAD model, which is used to find split points even when
+    /// `Opts::enable_voice_activity_detection` is off.
+    ParallelChunks {
+        workers: usize,
+        chunk_seconds: usize,
+    },
+}
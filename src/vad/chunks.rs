@@ -0,0 +1,77 @@
+use std::ops::Range;
+
+/// Split a buffer into contiguous chunks, cutting only inside silences between speech ranges.
+///
+/// - `speech` must be sorted and non-overlapping (as returned by `VadProcessor::speech_ranges`).
+/// - A chunk grows until it is at least `target_len` samples long; the cut is then placed in the
+///   middle of the next silence, so speech is never split across chunks.
+/// - The chunks cover `0..total_len` without gaps, keeping timestamps easy to map back.
+///
+/// A single speech range longer than `target_len` yields a chunk longer than the target.
+pub(crate) fn split_at_silences(
+    speech: &[(usize, usize)],
+    total_len: usize,
+    target_len: usize,
+) -> Vec<Range<usize>> {
+    if total_len == 0 {
+        return Vec::new();
+    }
+
+    let target_len = target_len.max(1);
+    let mut chunks = Vec::new();
+    let mut chunk_start = 0usize;
+
+    for pair in speech.windows(2) {
+        let (_, silence_start) = pair[0];
+        let (silence_end, _) = pair[1];
+        if silence_end <= silence_start {
+            continue;
+        }
+
+        let cut = (silence_start + (silence_end - silence_start) / 2).min(total_len);
+        if cut.saturating_sub(chunk_start) >= target_len {
+            chunks.push(chunk_start..cut);
+            chunk_start = cut;
+        }
+    }
+
+    chunks.push(chunk_start..total_len);
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_at_silences_empty_buffer_has_no_chunks() {
+        assert!(split_at_silences(&[], 0, 10).is_empty());
+    }
+
+    #[test]
+    fn split_at_silences_without_speech_keeps_one_chunk() {
+        assert_eq!(split_at_silences(&[], 100, 10), vec![0..100]);
+    }
+
+    #[test]
+    fn split_at_silences_cuts_in_the_middle_of_gaps() {
+        let speech = [(0, 40), (60, 90), (110, 200)];
+        assert_eq!(
+            split_at_silences(&speech, 200, 40),
+            vec![0..50, 50..100, 100..200]
+        );
+    }
+
+    #[test]
+    fn split_at_silences_groups_short_ranges_up_to_target() {
+        let speech = [(0, 10), (20, 30), (40, 50), (60, 70)];
+        assert_eq!(split_at_silences(&speech, 80, 30), vec![0..35, 35..80]);
+    }
+
+    #[test]
+    fn split_at_silences_never_cuts_inside_speech() {
+        // One long speech run exceeds the target; it stays whole.
+        let speech = [(0, 500), (520, 540)];
+        assert_eq!(split_at_silences(&speech, 540, 100), vec![0..510, 510..540]);
+    }
+}
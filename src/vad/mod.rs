@@ -7,9 +7,11 @@
 //! machinery. This keeps public APIs unsurprising and preserves explicit control flow at call
 //! sites.
 
+mod chunks;
 mod processor;
 mod stream;
 mod to_speech;
 
+pub(crate) use chunks::split_at_silences;
 pub use processor::VadProcessor;
 pub use stream::VadStreamReceiver;
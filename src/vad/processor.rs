@@ -1,7 +1,10 @@
 use anyhow::Result;
 use whisper_rs::{WhisperVadContext, WhisperVadContextParams};
 
-use super::to_speech::{DEFAULT_VAD_POLICY, VadPolicy, to_speech_only_with_policy};
+use super::to_speech::{
+    DEFAULT_VAD_POLICY, VadPolicy, apply_non_speech_gain_in_place, speech_ranges,
+    to_speech_only_with_policy,
+};
 
 /// Voice Activity Detection (VAD) processor.
 ///
@@ -31,4 +34,24 @@ impl VadProcessor {
     pub fn apply(&mut self, samples_16k_mono: &mut [f32]) -> Result<bool> {
         to_speech_only_with_policy(&mut self.ctx, 16_000, samples_16k_mono, self.policy)
     }
+
+    /// Detect speech without modifying the buffer.
+    ///
+    /// Returns sorted, non-overlapping sample ranges (padded and merged per policy); empty when
+    /// no speech is found.
+    pub(crate) fn speech_ranges(
+        &mut self,
+        samples_16k_mono: &[f32],
+    ) -> Result<Vec<(usize, usize)>> {
+        speech_ranges(&mut self.ctx, 16_000, samples_16k_mono, self.policy)
+    }
+
+    /// Attenuate everything outside `ranges`, as `apply` does after detection.
+    pub(crate) fn attenuate_non_speech(
+        &self,
+        samples_16k_mono: &mut [f32],
+        ranges: &[(usize, usize)],
+    ) {
+        apply_non_speech_gain_in_place(samples_16k_mono, ranges, self.policy.non_speech_gain);
+    }
 }
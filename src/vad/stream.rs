@@ -1,4 +1,5 @@
-use std::sync::mpsc;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, Instant};
 
 use anyhow::{Result, anyhow};
 
@@ -161,27 +162,53 @@ impl VadStreamReceiver {
     /// - otherwise, pull more input;
     /// - once input ends, flush exactly once and drain any remaining output.
     pub fn recv(&mut self) -> Result<Vec<f32>> {
+        loop {
+            if let Some(chunk) = self.next_chunk(None)? {
+                return Ok(chunk);
+            }
+        }
+    }
+
+    /// Like [`recv`](Self::recv), but gives up after `timeout`.
+    ///
+    /// Returns `Ok(None)` if no chunk became ready in time; buffered VAD state is kept, so the
+    /// next call picks up where this one stopped.
+    pub fn recv_timeout(&mut self, timeout: Duration) -> Result<Option<Vec<f32>>> {
+        self.next_chunk(Some(Instant::now() + timeout))
+    }
+
+    fn next_chunk(&mut self, deadline: Option<Instant>) -> Result<Option<Vec<f32>>> {
         loop {
             if let Some(chunk) = self.vad.peek_chunk(self.emit_frames) {
                 let out = chunk.to_vec();
                 self.vad.consume_chunk(self.emit_frames);
-                return Ok(out);
+                return Ok(Some(out));
             }
 
             if self.flushed {
                 if let Some(rem) = self.vad.peek_remainder() {
                     let out = rem.to_vec();
                     self.vad.consume_remainder();
-                    return Ok(out);
+                    return Ok(Some(out));
                 }
                 return Err(anyhow!(
                     "vad input channel disconnected (all buffered audio drained)"
                 ));
             }
 
-            match self.inner.recv() {
+            let received = match deadline {
+                Some(deadline) => self
+                    .inner
+                    .recv_timeout(deadline.saturating_duration_since(Instant::now())),
+                None => self
+                    .inner
+                    .recv()
+                    .map_err(|_| RecvTimeoutError::Disconnected),
+            };
+            match received {
                 Ok(chunk) => self.vad.push(&chunk)?,
-                Err(_) => {
+                Err(RecvTimeoutError::Timeout) => return Ok(None),
+                Err(RecvTimeoutError::Disconnected) => {
                     // Flush once when the input channel closes so VAD can emit buffered
                     // context/padding. After this, only drain output until exhausted.
                     self.vad.flush()?;
@@ -18,6 +18,26 @@ pub fn to_speech_only_with_policy(
     samples: &mut [f32],
     policy: VadPolicy,
 ) -> Result<bool> {
+    let ranges = speech_ranges(ctx, sample_rate_hz, samples, policy)?;
+    if ranges.is_empty() {
+        return Ok(false);
+    }
+
+    // Attenuate non-speech regions in-place (preserves buffer length).
+    apply_non_speech_gain_in_place(samples, &ranges, policy.non_speech_gain);
+    Ok(true)
+}
+
+/// Run VAD and return the selected speech ranges (sample indices) without touching `samples`.
+///
+/// Ranges are padded, filtered, and merged according to `policy`; they are sorted and
+/// non-overlapping. Returns an empty list when no speech is found.
+pub fn speech_ranges(
+    ctx: &mut WhisperVadContext,
+    sample_rate_hz: u32,
+    samples: &[f32],
+    policy: VadPolicy,
+) -> Result<Vec<(usize, usize)>> {
     // Build VAD parameters from defaults and apply our policy knobs.
     let mut vad_params = WhisperVadParams::default();
 
@@ -32,14 +52,8 @@ pub fn to_speech_only_with_policy(
     let segments = ctx.segments_from_samples(vad_params, samples)?;
 
     // Convert segments -> merged/filtered/padded sample ranges.
-    let Some(ranges) = speech_ranges_with_policy(sample_rate_hz, &segments, samples, policy)?
-    else {
-        return Ok(false);
-    };
-
-    // Attenuate non-speech regions in-place (preserves buffer length).
-    apply_non_speech_gain_in_place(samples, &ranges, policy.non_speech_gain);
-    Ok(true)
+    let ranges = speech_ranges_with_policy(sample_rate_hz, &segments, samples, policy)?;
+    Ok(ranges.unwrap_or_default())
 }
 
 /// Compute speech ranges (sample indices) according to `segments` and `policy`.
@@ -112,7 +126,7 @@ fn speech_ranges_with_policy(
 ///
 /// - `ranges` must be sorted and non-overlapping (the builder guarantees this).
 /// - `gain` is clamped to [0.0, 1.0]
-fn apply_non_speech_gain_in_place(samples: &mut [f32], ranges: &[(usize, usize)], gain: f32) {
+pub fn apply_non_speech_gain_in_place(samples: &mut [f32], ranges: &[(usize, usize)], gain: f32) {
     let gain = gain.clamp(0.0, 1.0);
 
     // If gain == 1.0, no change needed.
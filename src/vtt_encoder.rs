@@ -61,8 +61,11 @@ impl<W: Write> SegmentEncoder for VttEncoder<W> {
         // Cue timing line.
         writeln!(&mut self.w, "{start} --> {end}")?;
 
-        // Cue text is written verbatim.
-        writeln!(&mut self.w, "{}", seg.text)?;
+        // Cue text is written verbatim; per-channel segments get a voice span naming the channel.
+        match seg.channel {
+            Some(channel) => writeln!(&mut self.w, "<v Channel {channel}>{}", seg.text)?,
+            None => writeln!(&mut self.w, "{}", seg.text)?,
+        }
 
         // Blank line separates cues.
         writeln!(&mut self.w)?;
@@ -115,9 +118,7 @@ mod tests {
             start_seconds: start,
             end_seconds: end,
             text: text.to_string(),
-            tokens: Vec::new(),
-            language_code: "en".to_string(),
-            next_speaker_turn: false,
+            ..Segment::default()
         }
     }
 
@@ -147,6 +148,21 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn vtt_labels_per_channel_cues_with_a_voice_span() -> anyhow::Result<()> {
+        let mut out = Vec::new();
+        let mut enc = VttEncoder::new(&mut out);
+
+        enc.write_segment(&Segment {
+            channel: Some(1),
+            ..seg(0.0, 1.0, "hello")
+        })?;
+        enc.close()?;
+
+        assert!(std::str::from_utf8(&out)?.contains("00:00:01.000\n<v Channel 1>hello\n\n"));
+        Ok(())
+    }
+
     #[test]
     fn vtt_format_timestamp_rounds_to_nearest_millisecond() {
         assert_eq!(format_timestamp_vtt(0.0004), "00:00:00.000");
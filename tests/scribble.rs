@@ -1,6 +1,6 @@
 use std::path::Path;
 
-use scribble::{Opts, OutputType, Scribble, WhisperBackend};
+use scribble::{Opts, Scribble, WhisperBackend};
 
 const FIXTURE_WAV: &str = "tests/fixtures/jfk.wav";
 const WHISPER_MODEL: &str = "./models/ggml-tiny.bin";
@@ -47,48 +47,27 @@ fn transcribes_wav_to_json_variants() -> anyhow::Result<()> {
     };
 
     let cases = [
-        (
-            "default",
-            Opts {
-                model_key: None,
-                enable_translate_to_english: false,
-                enable_voice_activity_detection: false,
-                language: None,
-                output_type: OutputType::Json,
-                incremental_min_window_seconds: 1,
-            },
-        ),
+        ("default", Opts::default()),
         (
             "with_vad",
             Opts {
-                model_key: None,
-                enable_translate_to_english: false,
                 enable_voice_activity_detection: true,
-                language: None,
-                output_type: OutputType::Json,
-                incremental_min_window_seconds: 1,
+                ..Opts::default()
             },
         ),
         (
             "with_language",
             Opts {
-                model_key: None,
-                enable_translate_to_english: false,
-                enable_voice_activity_detection: false,
                 language: Some("en".to_string()),
-                output_type: OutputType::Json,
-                incremental_min_window_seconds: 1,
+                ..Opts::default()
             },
         ),
         (
             "with_vad_and_language",
             Opts {
-                model_key: None,
-                enable_translate_to_english: false,
                 enable_voice_activity_detection: true,
                 language: Some("en".to_string()),
-                output_type: OutputType::Json,
-                incremental_min_window_seconds: 1,
+                ..Opts::default()
             },
         ),
     ];
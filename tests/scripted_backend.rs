@@ -0,0 +1,69 @@
+//! End-to-end tests that run the full decode → transcribe → encode pipeline against
+//! `ScriptedBackend`, so they need no model files.
+#![cfg(feature = "testing")]
+
+use scribble::{Error, Opts, OutputType, Scribble, ScriptedBackend, TranscriptionMode};
+
+const FIXTURE_WAV: &str = "tests/fixtures/jfk.wav";
+
+fn opts(output_type: OutputType, transcription_mode: TranscriptionMode) -> Opts {
+    Opts {
+        output_type,
+        transcription_mode,
+        ..Opts::default()
+    }
+}
+
+fn transcribe(backend: ScriptedBackend, opts: &Opts) -> (scribble::Result<()>, String) {
+    let scribble = Scribble::with_backend(backend);
+    let wav = std::fs::File::open(FIXTURE_WAV).expect("open fixture WAV");
+    let mut out = Vec::new();
+    let res = scribble.transcribe(wav, &mut out, opts);
+    (res, String::from_utf8(out).expect("utf-8 output"))
+}
+
+#[test]
+fn scripted_segments_reach_the_encoder_in_every_mode() -> anyhow::Result<()> {
+    let backend = ScriptedBackend::new()
+        .text_at(16_000, 0.0, 1.0, " And so my fellow Americans")
+        .text_at(32_000, 1.0, 2.0, " ask not");
+
+    for mode in [
+        TranscriptionMode::Streaming,
+        TranscriptionMode::Full { max_seconds: 60 },
+    ] {
+        let (res, out) = transcribe(backend.clone(), &opts(OutputType::Json, mode));
+        res?;
+
+        let parsed: serde_json::Value = serde_json::from_str(&out)?;
+        let texts: Vec<_> = parsed
+            .as_array()
+            .expect("JSON array")
+            .iter()
+            .map(|seg| seg["text"].as_str().unwrap_or_default().to_owned())
+            .collect();
+        assert_eq!(
+            texts,
+            [" And so my fellow Americans", " ask not"],
+            "{mode:?}"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn scripted_errors_surface_as_backend_errors() {
+    let backend = ScriptedBackend::new()
+        .text_at(0, 0.0, 1.0, " hello")
+        .error_at(16_000, "injected failure");
+
+    let (res, out) = transcribe(
+        backend,
+        &opts(OutputType::Vtt, TranscriptionMode::Streaming),
+    );
+
+    let err = res.unwrap_err();
+    assert!(matches!(err, Error::Backend { .. }), "{err:?}");
+    assert!(err.to_string().contains("injected failure"));
+    assert!(out.contains("hello"));
+}